@@ -0,0 +1,77 @@
+//! Benchmarks of each day's hot path against its real puzzle input.
+//!
+//! Save a baseline with `cargo bench -p aoc -- --save-baseline main` and
+//! compare a change against it with `cargo bench -p aoc -- --baseline main`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use utils::{load, InputSource, Solution};
+
+/// Parses the default input of `S`, which must be present to benchmark it.
+fn input<S: Solution>() -> S::Input {
+    load::<S>(&InputSource::Default(S::DAY)).unwrap_or_else(|e| panic!("{e}"))
+}
+
+fn day1(c: &mut Criterion) {
+    let lines = input::<day1::Day1>();
+    c.bench_function("day1 get_calibration_value_2", |b| b.iter(|| {
+        lines.iter().map(|x| day1::get_calibration_value_2(black_box(x))).sum::<u32>()
+    }));
+}
+
+fn day2(c: &mut Criterion) {
+    let games = input::<day2::Day2>();
+    c.bench_function("day2 part2", |b| b.iter(|| day2::part2(black_box(&games))));
+}
+
+fn day3(c: &mut Criterion) {
+    let schematic = input::<day3::Day3>();
+    c.bench_function("day3 find_adjacent_gears", |b| {
+        b.iter(|| day3::find_adjacent_gears(black_box(&schematic)))
+    });
+}
+
+fn day4(c: &mut Criterion) {
+    let cards = input::<day4::Day4>();
+    c.bench_function("day4 get_multiplying_card_points", |b| {
+        b.iter(|| day4::get_multiplying_card_points(black_box(&cards)))
+    });
+}
+
+fn day5(c: &mut Criterion) {
+    let almanac = input::<day5::Day5>();
+    c.bench_function("day5 part2", |b| b.iter(|| day5::part2(black_box(&almanac))));
+}
+
+fn day6(c: &mut Criterion) {
+    let races = input::<day6::Day6>();
+    c.bench_function("day6 count_winning_options", |b| b.iter(|| {
+        races.folded().iter().map(|x| black_box(x).count_winning_options()).sum::<u64>()
+    }));
+}
+
+fn day7(c: &mut Criterion) {
+    let hands = input::<day7::Day7>();
+    c.bench_function("day7 get_winnings", |b| {
+        b.iter(|| day7::get_winnings(black_box(&hands)))
+    });
+}
+
+/// Day 9 takes the differences while parsing, so the parse is timed too.
+fn day9(c: &mut Criterion) {
+    let source = InputSource::Default(day9::Day9::DAY);
+    let raw = utils::read::<day9::Day9>(&source).unwrap_or_else(|e| panic!("{e}"));
+    c.bench_function("day9 parse", |b| b.iter(|| day9::parse(black_box(&raw.text)).unwrap()));
+    let readings = input::<day9::Day9>();
+    c.bench_function("day9 part2", |b| b.iter(|| day9::part2(black_box(&readings))));
+}
+
+fn day11(c: &mut Criterion) {
+    let image = input::<day11::Day11>();
+    c.bench_function("day11 shortest_path", |b| {
+        b.iter(|| day11::shortest_path(black_box(image.galaxies()), 1_000_000))
+    });
+}
+
+criterion_group!(benches, day1, day2, day3, day4, day5, day6, day7, day9, day11);
+criterion_main!(benches);
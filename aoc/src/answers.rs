@@ -0,0 +1,163 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const HEADER: &str = "\
+# Confirmed puzzle answers, checked by `aoc verify`.
+# Record a new one with `aoc record <day> <part> <answer>`.
+";
+
+/// The confirmed answers to the two parts of one input.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DayAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+/// The confirmed answers for every day, as stored in `answers.toml`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers(BTreeMap<u8, DayAnswers>);
+
+/// How a computed answer compares to the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail(String),
+    Unknown,
+}
+
+impl DayAnswers {
+    /// Reads `part1 = "..."` and `part2 = "..."` lines, either of them
+    /// optional, as in a day's table of `answers.toml`.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        toml::from_str(s).map_err(|e| e.to_string())
+    }
+
+    /// Loads the answers at `path`, or `None` if there is no such file.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s).map(Some).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            _ => self.part2.as_deref(),
+        }
+    }
+
+    pub fn check(&self, part: u8, answer: &str) -> Status {
+        match self.get(part) {
+            Some(expected) if expected == answer => Status::Pass,
+            Some(expected) => Status::Fail(expected.to_owned()),
+            None => Status::Unknown,
+        }
+    }
+}
+
+/// Returns the path of `answers.toml` at the workspace root.
+pub fn default_path() -> PathBuf {
+    utils::workspace_dir().join("answers.toml")
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let raw: BTreeMap<String, DayAnswers> = toml::from_str(s)
+            .map_err(|e| e.to_string())?;
+        let mut answers = Self::default();
+        for (key, day) in raw {
+            let n = key.strip_prefix("day")
+                       .and_then(|x| x.parse::<u8>().ok())
+                       .ok_or_else(|| format!("invalid table `[{key}]`, expected `[day<N>]`"))?;
+            answers.0.insert(n, day);
+        }
+        Ok(answers)
+    }
+
+    /// Loads the answers at `path`, treating a missing file as empty.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_toml()).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&day)?.get(part)
+    }
+
+    pub fn set(&mut self, day: u8, part: u8, answer: &str) {
+        let d = self.0.entry(day).or_default();
+        let slot = match part {
+            1 => &mut d.part1,
+            _ => &mut d.part2,
+        };
+        *slot = Some(answer.to_owned());
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Status {
+        self.0.get(&day).map_or(Status::Unknown, |d| d.check(part, answer))
+    }
+
+    /// Serializes the answers with days in numeric rather than lexical order.
+    fn to_toml(&self) -> String {
+        let mut s = String::from(HEADER);
+        for (day, d) in &self.0 {
+            write!(s, "\n[day{day}]\n").unwrap();
+            if let Some(a) = &d.part1 { writeln!(s, "part1 = {a:?}").unwrap() }
+            if let Some(a) = &d.part2 { writeln!(s, "part2 = {a:?}").unwrap() }
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut a = Answers::default();
+        a.set(11, 1, "9565386");
+        a.set(2, 2, "69929");
+        a.set(2, 1, "2164");
+        let s = a.to_toml();
+        assert!(s.find("[day2]").unwrap() < s.find("[day11]").unwrap());
+        assert_eq!(Answers::parse(&s).unwrap(), a);
+    }
+
+    #[test]
+    fn check() {
+        let a = Answers::parse("[day7]\npart1 = \"250347426\"\n").unwrap();
+        assert_eq!(a.check(7, 1, "250347426"), Status::Pass);
+        assert_eq!(a.check(7, 1, "1"), Status::Fail("250347426".to_owned()));
+        assert_eq!(a.check(7, 2, "1"), Status::Unknown);
+        assert_eq!(a.check(8, 1, "1"), Status::Unknown);
+    }
+
+    #[test]
+    fn single_input() {
+        let a = DayAnswers::parse("part2 = \"5905\"\n").unwrap();
+        assert_eq!(a.check(2, "5905"), Status::Pass);
+        assert_eq!(a.check(2, "6440"), Status::Fail("5905".to_owned()));
+        assert_eq!(a.check(1, "6440"), Status::Unknown);
+        assert!(DayAnswers::parse("part3 = \"1\"\n").is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Answers::parse("[seven]\npart1 = \"1\"\n").is_err());
+        assert!(Answers::parse("[day7]\npart3 = \"1\"\n").is_err());
+    }
+}
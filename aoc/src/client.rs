@@ -0,0 +1,154 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const USER_AGENT: &str = "github.com/comed-ian/aoc_2023 runner";
+
+fn default_base_url() -> String { "https://adventofcode.com".to_owned() }
+fn default_year() -> u16 { 2023 }
+
+/// Settings for talking to the puzzle site, read from `.aoc/config.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The value of the site's `session` cookie.
+    pub session: String,
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
+    #[serde(default = "default_year")]
+    pub year: u16,
+}
+
+/// Returns the path of the config file, which must not be committed.
+pub fn default_config_path() -> PathBuf {
+    utils::workspace_dir().join(".aoc").join("config.toml")
+}
+
+impl Config {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        toml::from_str(s).map_err(|e| e.to_string())
+    }
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let s = fs::read_to_string(path).map_err(|e| {
+            format!("{}: {e}, create it with `session = \"<cookie>\"`", path.display())
+        })?;
+        Self::parse(&s).map_err(|e| format!("{}: {e}", path.display()))
+    }
+}
+
+/// A blocking client for the puzzle site.
+pub struct Client {
+    config: Config,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(30)))
+            .user_agent(USER_AGENT)
+            .build()
+            .into();
+        Self { config, agent }
+    }
+
+    /// Returns the URL of `page` below the puzzle for `day`.
+    pub fn url(&self, day: u8, page: &str) -> String {
+        format!("{}/{}/day/{day}/{page}",
+                self.config.base_url.trim_end_matches('/'), self.config.year)
+    }
+
+    fn cookie(&self) -> String { format!("session={}", self.config.session) }
+
+    /// Downloads the puzzle input for `day`.
+    pub fn input(&self, day: u8) -> Result<String, String> {
+        let url = self.url(day, "input");
+        self.agent.get(&url)
+            .header("Cookie", self.cookie())
+            .call()
+            .and_then(|mut r| r.body_mut().read_to_string())
+            .map_err(|e| format!("GET {url}: {e}"))
+    }
+
+    /// Submits `answer` to `part` of `day`, returning the response page.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<String, String> {
+        let url = self.url(day, "answer");
+        let level = part.to_string();
+        self.agent.post(&url)
+            .header("Cookie", self.cookie())
+            .send_form([("level", level.as_str()), ("answer", answer)])
+            .and_then(|mut r| r.body_mut().read_to_string())
+            .map_err(|e| format!("POST {url}: {e}"))
+    }
+}
+
+/// Downloads the input for `day` to `dest` unless the file already exists,
+/// replacing the empty placeholder of a new day. Returns whether anything
+/// was downloaded.
+pub fn fetch(client: &Client, day: u8, dest: &Path) -> Result<bool, String> {
+    if fs::metadata(dest).is_ok_and(|x| x.len() > 0) { return Ok(false) }
+    let input = client.input(day)?;
+    if let Some(dir) = dest.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+    }
+    // write next to the destination first so an interrupted download is
+    // never mistaken for a cached input
+    let partial = dest.with_extension("part");
+    fs::write(&partial, input)
+        .and_then(|_| fs::rename(&partial, dest))
+        .map_err(|e| format!("{}: {e}", dest.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::Stub;
+
+    fn client(base_url: String) -> Client {
+        Client::new(Config { session: "abc".to_owned(), base_url, year: 2023 })
+    }
+
+    #[test]
+    fn config() {
+        let c = Config::parse("session = \"abc\"\n").unwrap();
+        assert_eq!(c.base_url, "https://adventofcode.com");
+        assert_eq!(c.year, 2023);
+        let c = Config::parse("session = \"abc\"\nbase_url = \"http://localhost:1/\"\nyear = 2022\n")
+                    .unwrap();
+        assert_eq!(Client::new(c).url(5, "input"), "http://localhost:1/2022/day/5/input");
+        assert!(Config::parse("year = 2022\n").is_err());
+    }
+
+    #[test]
+    fn fetch_caches() {
+        let stub = Stub::start(vec![(200, "1 2 3\n".to_owned())]);
+        let client = client(stub.url());
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let dest = dir.join("day9").join("input.txt");
+        _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dest.parent().unwrap()).unwrap();
+        fs::write(&dest, "").unwrap();
+
+        assert!(fetch(&client, 9, &dest).unwrap());
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "1 2 3\n");
+        // the stub only answers once, so a second download would fail
+        assert!(!fetch(&client, 9, &dest).unwrap());
+
+        let requests = stub.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2023/day/9/input HTTP/1.1\r\n"));
+        assert!(requests[0].to_lowercase().contains("cookie: session=abc\r\n"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn fetch_error() {
+        let stub = Stub::start(vec![(404, "not found".to_owned())]);
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-404-{}", std::process::id()));
+        let dest = dir.join("input.txt");
+        assert!(fetch(&client(stub.url()), 1, &dest).unwrap_err().contains("404"));
+        assert!(!dest.exists());
+    }
+}
@@ -0,0 +1,59 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::answers::DayAnswers;
+
+/// One of the puzzle inputs of a directory, with the answers recorded next
+/// to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    /// The file name without its `.txt` extension.
+    pub name: String,
+    pub path: PathBuf,
+    /// The contents of `<name>.answers`, if there is such a file.
+    pub expected: Option<DayAnswers>,
+}
+
+/// Lists the `*.txt` files of `dir` by name, reading the `<name>.answers`
+/// file beside each, in the format of [`DayAnswers::parse`].
+pub fn cases(dir: &Path) -> Result<Vec<Case>, String> {
+    let err = |e: std::io::Error| format!("{}: {e}", dir.display());
+    let mut cases: Vec<Case> = Vec::new();
+    for entry in fs::read_dir(dir).map_err(err)? {
+        let path = entry.map_err(err)?.path();
+        if path.extension().is_none_or(|x| x != "txt") || !path.is_file() { continue }
+        let Some(name) = path.file_stem().and_then(|x| x.to_str()) else { continue };
+        let expected = DayAnswers::load(&path.with_extension("answers"))?;
+        cases.push(Case { name: name.to_owned(), path, expected });
+    }
+    cases.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(cases)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc-compare-{}", std::process::id()));
+        _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("nested.txt")).unwrap();
+        for (f, contents) in [("bob.txt", "b"), ("alice.txt", "a"), ("notes.md", ""),
+                              ("alice.answers", "part1 = \"288\"\n"), ("carol.answers", "")] {
+            fs::write(dir.join(f), contents).unwrap();
+        }
+
+        let cases = cases(&dir).unwrap();
+        let names: Vec<&str> = cases.iter().map(|x| x.name.as_str()).collect();
+        assert_eq!(names, ["alice", "bob"]);
+        assert_eq!(cases[0].path, dir.join("alice.txt"));
+        assert_eq!(cases[0].expected, Some(DayAnswers::parse("part1 = \"288\"").unwrap()));
+        assert_eq!(cases[1].expected, None);
+
+        fs::write(dir.join("bob.answers"), "part1 = 288\n").unwrap();
+        assert!(super::cases(&dir).unwrap_err().contains("bob.answers"));
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(super::cases(&dir).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use tracing::info_span;
+use utils::render::Picture;
+use utils::{load, read, Error, InputSource, Solution};
+
+/// The answer to a single part of a puzzle and how long it took to solve.
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+/// What to make of the input besides the answers.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Extras {
+    /// A drawing for the terminal.
+    pub drawing: bool,
+    /// A picture to save as an image file.
+    pub picture: bool,
+}
+
+/// The answers of one day, with the extras that were asked for if the day
+/// supports them.
+#[derive(Debug, Clone, Default)]
+pub struct Solved {
+    pub parts: Vec<PartResult>,
+    pub drawing: Option<String>,
+    pub picture: Option<Picture>,
+}
+
+/// Loads the input from `source` and solves the requested parts of one day.
+pub type Solver = fn(&InputSource, &[u8], Extras) -> Result<Solved, Error>;
+
+/// How long each stage of one day took over repeated runs.
+#[derive(Debug, Clone, Default)]
+pub struct Samples {
+    pub parse: Vec<Duration>,
+    pub part1: Vec<Duration>,
+    pub part2: Vec<Duration>,
+}
+
+/// Loads the input from `source` once and times every stage `repeat` times.
+pub type Timer = fn(&InputSource, usize) -> Result<Samples, Error>;
+
+/// A day crate linked into the runner.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    pub solve: Solver,
+    pub time: Timer,
+}
+
+fn solve<S: Solution>(source: &InputSource, parts: &[u8], extras: Extras) ->
+            Result<Solved, Error> {
+    let _day = info_span!("day", day = S::DAY).entered();
+    let input = info_span!("parse").in_scope(|| load::<S>(source))?;
+    let drawing = if extras.drawing { S::visualize(&input) } else { None };
+    let picture = if extras.picture { S::picture(&input) } else { None };
+    let parts = parts.iter().map(|&part| {
+        let _part = info_span!("part", part).entered();
+        let start = Instant::now();
+        let answer = match part {
+            1 => S::part1(&input).to_string(),
+            _ => S::part2(&input).to_string(),
+        };
+        PartResult { part, answer, elapsed: start.elapsed() }
+    }).collect();
+    Ok(Solved { parts, drawing, picture })
+}
+
+fn time<S: Solution>(source: &InputSource, repeat: usize) -> Result<Samples, Error> {
+    let data = read::<S>(source)?;
+    let mut samples = Samples::default();
+    for _ in 0..repeat {
+        let start = Instant::now();
+        let input = S::parse(black_box(&data.text)).map_err(|e| e.in_file(&data.name))?;
+        samples.parse.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part1(black_box(&input)));
+        samples.part1.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part2(black_box(&input)));
+        samples.part2.push(start.elapsed());
+    }
+    Ok(samples)
+}
+
+const fn day<S: Solution>() -> Day {
+    Day { day: S::DAY, solve: solve::<S>, time: time::<S> }
+}
+
+/// Every implemented day, in puzzle order.
+pub const DAYS: &[Day] = &[
+    day::<day1::Day1>(),
+    day::<day2::Day2>(),
+    day::<day3::Day3>(),
+    day::<day4::Day4>(),
+    day::<day5::Day5>(),
+    day::<day6::Day6>(),
+    day::<day7::Day7>(),
+    day::<day9::Day9>(),
+    day::<day11::Day11>(),
+];
+
+/// Looks up a registered day by its puzzle number.
+pub fn find(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|x| x.day == day)
+}
@@ -0,0 +1,440 @@
+mod answers;
+mod client;
+mod compare;
+mod days;
+mod scaffold;
+#[cfg(test)]
+mod stub;
+mod submit;
+mod table;
+mod timing;
+
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use utils::{input_path, workspace_dir, InputSource};
+
+use answers::{Answers, Status};
+use client::{Client, Config};
+use days::{Day, Extras, PartResult, DAYS};
+use utils::render::Picture;
+use submit::{Submissions, Verdict};
+use table::{Align, Table};
+use timing::{DayTiming, Report};
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2023 runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Explain the solvers' steps on stderr, `-vv` for every detail.
+    /// Without it `RUST_LOG` picks what is shown
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a single day, or every implemented day with `all`
+    Run {
+        /// Day number, or `all`
+        day: Selection,
+        /// Only solve this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Input file instead of `src/day<N>/input.txt`, or `-` for stdin.
+        /// `{day}` is replaced by the day number. Defaults to `$AOC_INPUT`.
+        #[arg(long)]
+        input: Option<String>,
+        /// Also draw the input in colour, highlighting what the answers are
+        /// made of, for the days that support it
+        #[arg(long)]
+        visualize: bool,
+        /// Also save a picture of the input, as for `--visualize`, to this
+        /// `.png`, `.svg` or `.gif` file. `{day}` is replaced by the day number.
+        #[arg(long)]
+        export: Option<String>,
+        /// Size in pixels of each cell of an exported picture
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u64).range(1..=64))]
+        scale: u64,
+    },
+    /// Time parsing and each part separately over repeated runs
+    Time {
+        /// Day number, or `all`
+        #[arg(default_value = "all")]
+        day: Selection,
+        /// Number of runs to take the median and minimum over
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+        repeat: u64,
+        /// Also write the results as JSON to this file
+        #[arg(long)]
+        json: Option<PathBuf>,
+    },
+    /// Solve and compare the answers against `answers.toml`
+    Verify {
+        /// Day number, or `all`
+        #[arg(default_value = "all")]
+        day: Selection,
+        /// Only verify this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+    },
+    /// Solve every `*.txt` input of a directory, comparing the answers
+    /// against the `<name>.answers` file beside each when there is one
+    Compare {
+        /// Day number, or `all`
+        day: Selection,
+        /// Directory of inputs. `{day}` is replaced by the day number
+        dir: String,
+        /// Only solve this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+    },
+    /// Create `src/day<N>` from a template and register it in the workspace
+    New {
+        day: u8,
+    },
+    /// Download a day's input to `src/day<N>/input.txt` unless it exists
+    Fetch {
+        day: u8,
+    },
+    /// Submit an answer, solving the part if no answer is given
+    Submit {
+        day: u8,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: Option<String>,
+    },
+    /// Record a confirmed answer in `answers.toml`
+    Record {
+        day: u8,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Selection {
+    All,
+    Day(u8),
+}
+
+impl FromStr for Selection {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Self::All),
+            _ => s.parse::<u8>()
+                  .map(Self::Day)
+                  .map_err(|_| format!("expected a day number or `all`, got `{s}`")),
+        }
+    }
+}
+
+impl Selection {
+    fn days(self) -> Result<Vec<&'static Day>, String> {
+        match self {
+            Self::All => Ok(DAYS.iter().collect()),
+            Self::Day(d) => days::find(d)
+                .map(|x| vec![x])
+                .ok_or_else(|| format!("day {d} is not implemented")),
+        }
+    }
+}
+
+fn parts(part: Option<u8>) -> Vec<u8> {
+    match part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    }
+}
+
+/// Where the selected days read their input from.
+#[derive(Debug, Clone, Copy)]
+enum Inputs<'a> {
+    /// The `--input` argument, else `$AOC_INPUT`, else the day's own input.
+    Given(Option<&'a str>),
+    /// Always the day's own puzzle input, whose answers are checked or
+    /// submitted.
+    Puzzle,
+}
+
+impl Inputs<'_> {
+    /// The input of `day`, with `var` as the value of `AOC_INPUT`.
+    fn source(self, day: u8, var: Option<&str>) -> InputSource {
+        match self {
+            Self::Given(input) => InputSource::choose(day, input, var),
+            Self::Puzzle => InputSource::Default(day),
+        }
+    }
+}
+
+/// The answers of the selected days, and the extras of those that support
+/// them.
+struct Solutions {
+    results: Vec<(u8, PartResult)>,
+    drawings: Vec<(u8, String)>,
+    pictures: Vec<(u8, Picture)>,
+}
+
+fn solve(selection: Selection, part: Option<u8>, inputs: Inputs, extras: Extras) ->
+         Result<Solutions, String> {
+    let parts = parts(part);
+    let days = selection.days()?;
+    let var = utils::input_var();
+    let sources: Vec<InputSource> = days.iter()
+                                        .map(|d| inputs.source(d.day, var.as_deref()))
+                                        .collect();
+    if let Some(s) = sources.iter().skip(1).find(|x| **x == sources[0]) {
+        return Err(format!("every day would read {s}, add `{{day}}` to the input path"));
+    }
+    let mut results: Vec<(u8, PartResult)> = Vec::new();
+    let mut drawings: Vec<(u8, String)> = Vec::new();
+    let mut pictures: Vec<(u8, Picture)> = Vec::new();
+    let single = matches!(selection, Selection::Day(_));
+    for (d, source) in days.iter().zip(sources) {
+        let res = (d.solve)(&source, &parts, extras).map_err(|e| e.to_string())?;
+        results.extend(res.parts.into_iter().map(|r| (d.day, r)));
+        match res.drawing {
+            Some(x) => drawings.push((d.day, x)),
+            None if extras.drawing && single => {
+                eprintln!("note: day {} has no visualization", d.day);
+            },
+            None => {},
+        }
+        match res.picture {
+            Some(x) => pictures.push((d.day, x)),
+            None if extras.picture && single => eprintln!("note: day {} has no picture", d.day),
+            None => {},
+        }
+    }
+    Ok(Solutions { results, drawings, pictures })
+}
+
+/// Where the picture of `day` is saved.
+fn export_path(export: &str, day: u8) -> PathBuf {
+    PathBuf::from(export.replace("{day}", &day.to_string()))
+}
+
+fn run(selection: Selection, part: Option<u8>, input: Option<&str>, visualize: bool,
+       export: Option<&str>, scale: usize) -> Result<bool, String> {
+    if let Some(path) = export {
+        if matches!(selection, Selection::All) && !path.contains("{day}") {
+            return Err(format!("every day would save its picture to {path}, \
+                                add `{{day}}` to the path"));
+        }
+    }
+    let mut table = Table::new(&[
+        ("day", Align::Right),
+        ("part", Align::Right),
+        ("answer", Align::Left),
+        ("time", Align::Right),
+    ]);
+    let extras = Extras { drawing: visualize, picture: export.is_some() };
+    let solutions = solve(selection, part, Inputs::Given(input), extras)?;
+    for (day, r) in solutions.results {
+        table.push(vec![
+            day.to_string(),
+            r.part.to_string(),
+            r.answer,
+            format!("{:.3?}", r.elapsed),
+        ]);
+    }
+    print!("{table}");
+    for (day, drawing) in solutions.drawings {
+        print!("\nday {day}\n{drawing}");
+    }
+    for (day, picture) in solutions.pictures {
+        let path = export_path(export.unwrap_or_default(), day);
+        picture.save(&path, scale).map_err(|e| e.to_string())?;
+        println!("saved {}", path.display());
+    }
+    Ok(true)
+}
+
+fn time(selection: Selection, repeat: usize, json: Option<PathBuf>) ->
+        Result<bool, String> {
+    let mut days: Vec<DayTiming> = Vec::new();
+    let var = utils::input_var();
+    for d in selection.days()? {
+        let source = Inputs::Given(None).source(d.day, var.as_deref());
+        let samples = (d.time)(&source, repeat).map_err(|e| e.to_string())?;
+        days.push(DayTiming::new(d.day, &samples));
+    }
+    let report = Report::new(repeat, days);
+    if cfg!(debug_assertions) { eprintln!("note: timings of a debug build, use --release"); }
+    print!("{}", report.table());
+    if let Some(path) = json {
+        std::fs::write(&path, report.to_json())
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+    }
+    Ok(true)
+}
+
+fn verify(selection: Selection, part: Option<u8>) -> Result<bool, String> {
+    let answers = Answers::load(&answers::default_path())?;
+    let mut table = Table::new(&[
+        ("day", Align::Right),
+        ("part", Align::Right),
+        ("answer", Align::Left),
+        ("status", Align::Left),
+    ]);
+    let mut ok = true;
+    for (day, r) in solve(selection, part, Inputs::Puzzle, Extras::default())?.results {
+        let status = match answers.check(day, r.part, &r.answer) {
+            Status::Pass => "pass".to_owned(),
+            Status::Fail(expected) => {
+                ok = false;
+                format!("FAIL (expected {expected})")
+            },
+            Status::Unknown => "unknown".to_owned(),
+        };
+        table.push(vec![day.to_string(), r.part.to_string(), r.answer, status]);
+    }
+    print!("{table}");
+    Ok(ok)
+}
+
+fn compare(selection: Selection, part: Option<u8>, dir: &str) -> Result<bool, String> {
+    if matches!(selection, Selection::All) && !dir.contains("{day}") {
+        return Err(format!("every day would read {dir}, add `{{day}}` to the directory"));
+    }
+    let parts = parts(part);
+    let mut table = Table::new(&[
+        ("day", Align::Right),
+        ("input", Align::Left),
+        ("part", Align::Right),
+        ("answer", Align::Left),
+        ("status", Align::Left),
+    ]);
+    let mut ok = true;
+    for d in selection.days()? {
+        let dir = PathBuf::from(dir.replace("{day}", &d.day.to_string()));
+        // with `all`, only the days that have a directory of inputs
+        if matches!(selection, Selection::All) && !dir.is_dir() { continue }
+        let cases = compare::cases(&dir)?;
+        if cases.is_empty() { eprintln!("note: no *.txt inputs in {}", dir.display()) }
+        for case in cases {
+            let source = InputSource::Path(case.path);
+            let results = match (d.solve)(&source, &parts, Extras::default()) {
+                Ok(solved) => solved.parts,
+                Err(e) => {
+                    ok = false;
+                    table.push(vec![d.day.to_string(), case.name, "-".to_owned(), String::new(),
+                                    format!("ERROR {e}")]);
+                    continue;
+                },
+            };
+            for r in results {
+                let status = match case.expected.as_ref().map(|x| x.check(r.part, &r.answer)) {
+                    Some(Status::Pass) => "pass".to_owned(),
+                    Some(Status::Fail(expected)) => {
+                        ok = false;
+                        format!("FAIL (expected {expected})")
+                    },
+                    Some(Status::Unknown) | None => "unknown".to_owned(),
+                };
+                table.push(vec![d.day.to_string(), case.name.clone(), r.part.to_string(),
+                                r.answer, status]);
+            }
+        }
+    }
+    print!("{table}");
+    Ok(ok)
+}
+
+fn record(day: u8, part: u8, answer: &str) -> Result<bool, String> {
+    let path = answers::default_path();
+    let mut answers = Answers::load(&path)?;
+    if let Some(old) = answers.get(day, part) {
+        if old != answer { println!("replacing day {day} part {part} answer {old}"); }
+    }
+    answers.set(day, part, answer);
+    answers.save(&path)?;
+    println!("recorded day {day} part {part} answer {answer}");
+    Ok(true)
+}
+
+fn new(day: u8) -> Result<bool, String> {
+    scaffold::generate(&workspace_dir(), day)?;
+    println!("created src/day{day}, run `aoc fetch {day}` to download its input");
+    Ok(true)
+}
+
+fn fetch(day: u8) -> Result<bool, String> {
+    let client = Client::new(Config::load(&client::default_config_path())?);
+    let dest = workspace_dir().join(input_path(day));
+    if client::fetch(&client, day, &dest)? {
+        println!("downloaded {}", dest.display());
+    } else {
+        println!("{} already exists", dest.display());
+    }
+    Ok(true)
+}
+
+fn submit(day: u8, part: u8, answer: Option<String>) -> Result<bool, String> {
+    let answer = match answer {
+        Some(a) => a,
+        None => {
+            let extras = Extras::default();
+            solve(Selection::Day(day), Some(part), Inputs::Puzzle, extras)?.results.remove(0).1.answer
+        },
+    };
+    let path = submit::default_state_path();
+    let mut submissions = Submissions::load(&path)?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |x| x.as_secs());
+    submissions.check(day, part, &answer, now)
+               .map_err(|e| format!("not submitting: {e}"))?;
+
+    let client = Client::new(Config::load(&client::default_config_path())?);
+    println!("submitting {answer} for day {day} part {part}");
+    let (verdict, wait) = submit::parse_response(&client.submit(day, part, &answer)?);
+    submissions.record(day, part, &answer, &verdict, wait, now);
+    submissions.save(&path)?;
+    println!("{verdict}");
+    if verdict != Verdict::Correct { return Ok(false) }
+    record(day, part, &answer)
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    utils::init_tracing(cli.verbose);
+    let res = match cli.command {
+        Command::Run { day, part, input, visualize, export, scale } => {
+            run(day, part, input.as_deref(), visualize, export.as_deref(), scale as usize)
+        },
+        Command::Time { day, repeat, json } => time(day, repeat as usize, json),
+        Command::Verify { day, part } => verify(day, part),
+        Command::Compare { day, dir, part } => compare(day, part, &dir),
+        Command::New { day } => new(day),
+        Command::Fetch { day } => fetch(day),
+        Command::Submit { day, part, answer } => submit(day, part, answer),
+        Command::Record { day, part, answer } => record(day, part, &answer),
+    };
+    match res {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn puzzle_input_ignores_override() {
+        let var = Some("/tmp/example{day}.txt");
+        assert_eq!(Inputs::Given(None).source(5, var),
+                   InputSource::Path(PathBuf::from("/tmp/example5.txt")));
+        assert_eq!(Inputs::Given(Some("-")).source(5, var), InputSource::Stdin);
+        assert_eq!(Inputs::Given(None).source(5, None), InputSource::Default(5));
+        assert_eq!(Inputs::Puzzle.source(5, var), InputSource::Default(5));
+    }
+}
@@ -0,0 +1,217 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn cargo_toml(day: u8) -> String {
+    format!(r#"[package]
+name = "day{day}"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+tracing = "0.1"
+utils = {{ path = "../../utils" }}
+
+[features]
+# Compile `input.txt` into the binary instead of reading it at runtime.
+embed-inputs = []
+"#)
+}
+
+fn main_rs(day: u8) -> String {
+    format!(r#"use day{day}::Day{day};
+use utils::run;
+
+fn main() {{
+    run::<Day{day}>();
+}}
+"#)
+}
+
+fn lib_rs(day: u8) -> String {
+    format!(r#"//! Day {day}
+
+use utils::{{parse_lines, Error, Solution}};
+
+pub fn parse(input: &str) -> Result<Vec<String>, Error> {{
+    parse_lines(input, |x| Ok(x.to_owned()))
+}}
+
+pub fn part1(_lines: &[String]) -> u64 {{ 0 }}
+pub fn part2(_lines: &[String]) -> u64 {{ 0 }}
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    type Input = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const DAY: u8 = {day};
+    #[cfg(feature = "embed-inputs")]
+    const EMBEDDED: Option<&'static str> = Some(include_str!("../input.txt"));
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {{ parse(input) }}
+    fn part1(lines: &Self::Input) -> u64 {{ part1(lines) }}
+    fn part2(lines: &Self::Input) -> u64 {{ part2(lines) }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    const EXAMPLE: &str = "\
+";
+
+    #[test]
+    #[ignore = "add the puzzle example"]
+    fn part1_example() {{
+        let input = Day{day}::parse(EXAMPLE).unwrap();
+        assert_eq!(Day{day}::part1(&input), 0);
+    }}
+
+    #[test]
+    #[ignore = "add the puzzle example"]
+    fn part2_example() {{
+        let input = Day{day}::parse(EXAMPLE).unwrap();
+        assert_eq!(Day{day}::part2(&input), 0);
+    }}
+}}
+"#)
+}
+
+/// Inserts `line(day)` into `text` next to the lines already generated by
+/// `line` for other days, keeping them in day order.
+fn register(text: &str, line: impl Fn(u8) -> String, day: u8) -> Result<String, String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let new = line(day);
+    if lines.iter().any(|x| *x == new) { return Ok(text.to_owned()) }
+    let existing: Vec<(usize, u8)> = lines.iter().enumerate().filter_map(|(i, x)| {
+        (1..=25u8).find(|&d| *x == line(d)).map(|d| (i, d))
+    }).collect();
+    let idx = match existing.iter().rev().find(|(_, d)| *d < day) {
+        Some((i, _)) => i + 1,
+        None => existing.first().ok_or_else(|| format!("no line like `{}`", new.trim()))?.0,
+    };
+    lines.insert(idx, &new);
+    let mut s = lines.join("\n");
+    if text.ends_with('\n') { s.push('\n') }
+    Ok(s)
+}
+
+/// The contents of the file at `path` once `day` is registered with each of
+/// `lines`, leaving the file itself untouched.
+fn register_file(path: &Path, lines: &[&dyn Fn(u8) -> String], day: u8) ->
+                 Result<(PathBuf, String), String> {
+    let mut text = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    for line in lines {
+        text = register(&text, line, day).map_err(|e| format!("{}: {e}", path.display()))?;
+    }
+    Ok((path.to_owned(), text))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+    }
+    fs::write(path, contents).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Creates `src/day<N>` below `root` and registers it in the workspace
+/// members and with the runner. Its `input.txt` starts out empty, for the
+/// crate to build with `embed-inputs` until `aoc fetch` replaces it.
+pub fn generate(root: &Path, day: u8) -> Result<(), String> {
+    if !(1..=25).contains(&day) { return Err(format!("there is no day {day}")) }
+    let dir = root.join("src").join(format!("day{day}"));
+    if dir.exists() { return Err(format!("{} already exists", dir.display())) }
+
+    // every registration is checked before any file is written, so that a
+    // failure leaves the workspace as it was
+    let edits = [
+        register_file(&root.join("Cargo.toml"), &[&|d| format!("    \"src/day{d}\",")], day)?,
+        register_file(&root.join("aoc").join("Cargo.toml"), &[
+            &|d| format!("day{d} = {{ path = \"../src/day{d}\" }}"),
+            &|d| format!("    \"day{d}/embed-inputs\","),
+        ], day)?,
+        register_file(&root.join("aoc").join("src").join("days.rs"),
+                      &[&|d| format!("    day::<day{d}::Day{d}>(),")], day)?,
+    ];
+    for (path, text) in &edits {
+        fs::write(path, text).map_err(|e| format!("{}: {e}", path.display()))?;
+    }
+
+    write(&dir.join("Cargo.toml"), &cargo_toml(day))?;
+    write(&dir.join("src").join("main.rs"), &main_rs(day))?;
+    write(&dir.join("src").join("lib.rs"), &lib_rs(day))?;
+    write(&dir.join("input.txt"), "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_in_order() {
+        let line = |d: u8| format!("    \"src/day{d}\",");
+        let text = "members = [\n    \"aoc\",\n    \"src/day1\",\n    \"src/day9\",\n    \"utils\",\n]\n";
+        assert_eq!(register(text, line, 8).unwrap(),
+            "members = [\n    \"aoc\",\n    \"src/day1\",\n    \"src/day8\",\n    \"src/day9\",\n    \"utils\",\n]\n");
+        assert_eq!(register(text, line, 12).unwrap(),
+            "members = [\n    \"aoc\",\n    \"src/day1\",\n    \"src/day9\",\n    \"src/day12\",\n    \"utils\",\n]\n");
+        let text = "members = [\n    \"src/day3\",\n]\n";
+        assert_eq!(register(text, line, 2).unwrap(),
+            "members = [\n    \"src/day2\",\n    \"src/day3\",\n]\n");
+        assert_eq!(register(text, line, 3).unwrap(), text);
+        assert!(register("members = []\n", line, 3).is_err());
+    }
+
+    #[test]
+    fn generates_workspace_files() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        _ = fs::remove_dir_all(&root);
+        let ws = utils::workspace_dir();
+        for f in ["Cargo.toml", "aoc/Cargo.toml", "aoc/src/days.rs"] {
+            write(&root.join(f), &fs::read_to_string(ws.join(f)).unwrap()).unwrap();
+        }
+        generate(&root, 10).unwrap();
+
+        let members = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(members.contains("    \"src/day9\",\n    \"src/day10\",\n    \"src/day11\",\n"));
+        let days = fs::read_to_string(root.join("aoc/src/days.rs")).unwrap();
+        assert!(days.contains("day::<day9::Day9>(),\n    day::<day10::Day10>(),\n"));
+        let runner = fs::read_to_string(root.join("aoc/Cargo.toml")).unwrap();
+        assert!(runner.contains("day10 = { path = \"../src/day10\" }\nday11"));
+        assert!(runner.contains("\"day10/embed-inputs\",\n    \"day11/embed-inputs\","));
+        assert!(root.join("src/day10/src/lib.rs").exists());
+        assert_eq!(fs::read_to_string(root.join("src/day10/input.txt")).unwrap(), "");
+        assert!(generate(&root, 10).unwrap_err().contains("already exists"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn embeds_its_input() {
+        // the crate builds with `embed-inputs` as its `input.txt` exists
+        assert!(cargo_toml(10).contains("\n[features]\n# Compile `input.txt` into the binary \
+                                         instead of reading it at runtime.\nembed-inputs = []\n"));
+        assert!(cargo_toml(10).contains("utils = { path = \"../../utils\" }\n"));
+        assert!(lib_rs(10).contains("#[cfg(feature = \"embed-inputs\")]\n    const EMBEDDED: \
+                                     Option<&'static str> = Some(include_str!(\"../input.txt\"));"));
+        assert!(main_rs(10).contains("run::<Day10>();"));
+    }
+
+    #[test]
+    fn failed_registration_writes_nothing() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-fail-{}", std::process::id()));
+        _ = fs::remove_dir_all(&root);
+        let members = "[workspace]\nmembers = [\n    \"src/day9\",\n]\n";
+        write(&root.join("Cargo.toml"), members).unwrap();
+        write(&root.join("aoc/Cargo.toml"), "[dependencies]\n").unwrap();
+        write(&root.join("aoc/src/days.rs"), "    day::<day9::Day9>(),\n").unwrap();
+
+        assert!(generate(&root, 10).unwrap_err().contains("no line like `day10 = "));
+        assert_eq!(fs::read_to_string(root.join("Cargo.toml")).unwrap(), members);
+        assert!(!root.join("src/day10").exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,51 @@
+//! A minimal HTTP server standing in for the puzzle site in tests.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+pub struct Stub {
+    addr: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl Stub {
+    /// Serves each `(status, body)` response to one connection, in order.
+    pub fn start(responses: Vec<(u16, String)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = requests.clone();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let Ok((stream, _)) = listener.accept() else { return };
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 { break }
+                    let lower = line.to_lowercase();
+                    if let Some(v) = lower.strip_prefix("content-length:") {
+                        length = v.trim().parse().unwrap_or(0);
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" { break }
+                }
+                let mut content = vec![0u8; length];
+                _ = reader.read_exact(&mut content);
+                request.push_str(&String::from_utf8_lossy(&content));
+                log.lock().unwrap().push(request);
+                let mut stream = reader.into_inner();
+                _ = write!(stream, "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\n\
+                                    Connection: close\r\n\r\n{body}", body.len());
+            }
+        });
+        Self { addr, requests }
+    }
+
+    pub fn url(&self) -> String { format!("http://{}", self.addr) }
+
+    pub fn requests(&self) -> Vec<String> { self.requests.lock().unwrap().clone() }
+}
@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The site's verdict on a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// Another answer was submitted too recently.
+    RateLimited(Duration),
+    /// The part has already been solved, or part 1 has not been.
+    WrongLevel,
+    /// A response that could not be recognised, with its text.
+    Unknown(String),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "wrong, too high"),
+            Self::TooLow => write!(f, "wrong, too low"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::RateLimited(d) => write!(f, "rate limited, wait {}s", d.as_secs()),
+            Self::WrongLevel => write!(f, "not the current level, was it already solved?"),
+            Self::Unknown(s) => write!(f, "unrecognised response: {s}"),
+        }
+    }
+}
+
+/// Returns the text of the response's `<article>`, where the site puts its
+/// verdict, with markup removed and whitespace collapsed.
+fn article_text(html: &str) -> String {
+    let start = html.find("<article").unwrap_or(0);
+    let end = html[start..].find("</article>").map_or(html.len(), |x| start + x);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html[start..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => { in_tag = false; text.push(' ') },
+            _ if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses a wait such as `1m 5s` from "You have 1m 5s left to wait".
+fn parse_left_to_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+    let mut secs = 0u64;
+    for token in text[start..end].split(' ') {
+        let (n, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+        let n = n.parse::<u64>().ok()?;
+        secs += n * match unit {
+            "h" => 3600,
+            "m" => 60,
+            "s" => 1,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+/// Parses the lockout after a wrong answer, e.g. "please wait 5 minutes".
+fn parse_please_wait(text: &str) -> Option<Duration> {
+    let text = text.to_lowercase();
+    let start = text.find("please wait ")? + "please wait ".len();
+    let mut words = text[start..].split(' ');
+    let n = match words.next()? {
+        "one" => 1,
+        n => n.parse::<u64>().ok()?,
+    };
+    match words.next()? {
+        "minute" | "minutes" => Some(Duration::from_secs(n * 60)),
+        "second" | "seconds" => Some(Duration::from_secs(n)),
+        _ => None,
+    }
+}
+
+/// Interprets the page returned after submitting an answer, along with how
+/// long the site asks to wait before the next submission.
+pub fn parse_response(html: &str) -> (Verdict, Option<Duration>) {
+    let text = article_text(html);
+    let verdict = if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("your answer is too high") {
+        Verdict::TooHigh
+    } else if text.contains("your answer is too low") {
+        Verdict::TooLow
+    } else if text.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else if text.contains("You gave an answer too recently") {
+        let wait = parse_left_to_wait(&text).unwrap_or(Duration::from_secs(60));
+        return (Verdict::RateLimited(wait), Some(wait));
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown(text.clone())
+    };
+    (verdict, parse_please_wait(&text))
+}
+
+/// What is known about the wrong answers to one part.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Bounds {
+    /// The highest answer reported as too low.
+    too_low: Option<i64>,
+    /// The lowest answer reported as too high.
+    too_high: Option<i64>,
+    #[serde(default)]
+    wrong: Vec<String>,
+}
+
+/// Local record of submissions, used to refuse answers that are known to
+/// be wrong and to respect the site's rate limit.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submissions {
+    /// Unix time before which the site will not accept another answer.
+    #[serde(default)]
+    wait_until: u64,
+    #[serde(default)]
+    days: BTreeMap<String, BTreeMap<String, Bounds>>,
+}
+
+/// Returns the path of the submission record.
+pub fn default_state_path() -> PathBuf {
+    utils::workspace_dir().join(".aoc").join("submissions.toml")
+}
+
+impl Submissions {
+    /// Loads the record at `path`, treating a missing file as empty.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => toml::from_str(&s).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let s = toml::to_string(self).map_err(|e| e.to_string())?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+        }
+        fs::write(path, s).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    fn bounds(&self, day: u8, part: u8) -> Option<&Bounds> {
+        self.days.get(&format!("day{day}"))?.get(&format!("part{part}"))
+    }
+
+    /// Returns why `answer` should not be submitted at unix time `now`.
+    pub fn check(&self, day: u8, part: u8, answer: &str, now: u64) -> Result<(), String> {
+        if now < self.wait_until {
+            return Err(format!("rate limited, wait {}s", self.wait_until - now));
+        }
+        let Some(b) = self.bounds(day, part) else { return Ok(()) };
+        if b.wrong.iter().any(|x| x == answer) {
+            return Err(format!("{answer} was already rejected"));
+        }
+        if let Ok(n) = answer.parse::<i64>() {
+            if let Some(low) = b.too_low.filter(|&x| n <= x) {
+                return Err(format!("{answer} is too low, {low} already was"));
+            }
+            if let Some(high) = b.too_high.filter(|&x| n >= x) {
+                return Err(format!("{answer} is too high, {high} already was"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the site's response to `answer` submitted at unix time `now`.
+    pub fn record(&mut self, day: u8, part: u8, answer: &str, verdict: &Verdict,
+                  wait: Option<Duration>, now: u64) {
+        if let Some(wait) = wait { self.wait_until = now + wait.as_secs() }
+        if !matches!(verdict, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong) { return }
+        let b = self.days.entry(format!("day{day}"))
+                         .or_default()
+                         .entry(format!("part{part}"))
+                         .or_default();
+        b.wrong.push(answer.to_owned());
+        let Ok(n) = answer.parse::<i64>() else { return };
+        match verdict {
+            Verdict::TooHigh => b.too_high = Some(b.too_high.map_or(n, |x| x.min(n))),
+            Verdict::TooLow => b.too_low = Some(b.too_low.map_or(n, |x| x.max(n))),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{Client, Config};
+    use crate::stub::Stub;
+
+    fn page(text: &str) -> String {
+        format!("<html><body><main>\n<article><p>{text}</p></article>\n</main></body></html>")
+    }
+
+    #[test]
+    fn responses() {
+        let (v, wait) = parse_response(&page("That's the right answer!  You are \
+            <span class=\"day-success\">one gold star</span> closer."));
+        assert_eq!((v, wait), (Verdict::Correct, None));
+        let (v, wait) = parse_response(&page("That's not the right answer; your answer \
+            is too high.  If you're stuck, ... Please wait one minute before trying again."));
+        assert_eq!((v, wait), (Verdict::TooHigh, Some(Duration::from_secs(60))));
+        let (v, _) = parse_response(&page("That's not the right answer; your answer is too low."));
+        assert_eq!(v, Verdict::TooLow);
+        let (v, wait) = parse_response(&page("That's not the right answer.  Because you \
+            have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before \
+            trying again."));
+        assert_eq!((v, wait), (Verdict::Wrong, Some(Duration::from_secs(300))));
+        let (v, _) = parse_response(&page("You gave an answer too recently; you have to \
+            wait after submitting an answer before trying again.  You have 1m 5s left to wait."));
+        assert_eq!(v, Verdict::RateLimited(Duration::from_secs(65)));
+        let (v, _) = parse_response(&page("You don't seem to be solving the right level.  \
+            Did you already complete it?"));
+        assert_eq!(v, Verdict::WrongLevel);
+        let (v, _) = parse_response("<html>Puzzle inputs differ by user.</html>");
+        assert_eq!(v, Verdict::Unknown("Puzzle inputs differ by user.".to_owned()));
+    }
+
+    #[test]
+    fn bounds() {
+        let mut s = Submissions::default();
+        s.record(7, 2, "500", &Verdict::TooHigh, None, 0);
+        s.record(7, 2, "100", &Verdict::TooLow, Some(Duration::from_secs(60)), 0);
+        s.record(7, 2, "abc", &Verdict::Wrong, None, 0);
+        assert!(s.check(7, 2, "300", 30).unwrap_err().contains("wait 30s"));
+        assert_eq!(s.check(7, 2, "300", 60), Ok(()));
+        assert!(s.check(7, 2, "500", 60).unwrap_err().contains("rejected"));
+        assert!(s.check(7, 2, "501", 60).unwrap_err().contains("too high"));
+        assert!(s.check(7, 2, "99", 60).unwrap_err().contains("too low"));
+        assert!(s.check(7, 2, "abc", 60).is_err());
+        assert_eq!(s.check(7, 1, "99", 60), Ok(()));
+
+        let saved = toml::to_string(&s).unwrap();
+        assert_eq!(toml::from_str::<Submissions>(&saved).unwrap(), s);
+    }
+
+    #[test]
+    fn submit() {
+        let stub = Stub::start(vec![(200, page("That's not the right answer; \
+            your answer is too low."))]);
+        let client = Client::new(Config {
+            session: "abc".to_owned(),
+            base_url: stub.url(),
+            year: 2023,
+        });
+        let body = client.submit(7, 2, "251224870").unwrap();
+        assert_eq!(parse_response(&body).0, Verdict::TooLow);
+        let request = &stub.requests()[0];
+        assert!(request.starts_with("POST /2023/day/7/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=251224870"));
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// A plain-text table whose columns are padded to their widest cell.
+#[derive(Debug)]
+pub struct Table {
+    columns: Vec<(&'static str, Align)>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(columns: &[(&'static str, Align)]) -> Self {
+        Self { columns: columns.to_vec(), rows: Vec::new() }
+    }
+    pub fn push(&mut self, row: Vec<String>) {
+        assert_eq!(row.len(), self.columns.len());
+        self.rows.push(row);
+    }
+    fn widths(&self) -> Vec<usize> {
+        self.columns.iter().enumerate().map(|(i, (name, _))| {
+            self.rows.iter()
+                     .map(|r| r[i].chars().count())
+                     .fold(name.len(), usize::max)
+        }).collect()
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let widths = self.widths();
+        let header: Vec<String> = self.columns.iter()
+                                              .map(|(name, _)| name.to_string())
+                                              .collect();
+        for row in std::iter::once(&header).chain(self.rows.iter()) {
+            let cells: Vec<String> = row.iter().enumerate().map(|(i, cell)| {
+                let width = widths[i];
+                match self.columns[i].1 {
+                    Align::Left => format!("{cell:<width$}"),
+                    Align::Right => format!("{cell:>width$}"),
+                }
+            }).collect();
+            writeln!(f, "{}", cells.join("  ").trim_end())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::days::Samples;
+use crate::table::{Align, Table};
+
+/// Summary statistics of one stage's samples, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut ns: Vec<u64> = samples.iter().map(|x| x.as_nanos() as u64).collect();
+        ns.sort_unstable();
+        let median = match ns.len() {
+            0 => 0,
+            n if n % 2 == 0 => (ns[n / 2 - 1] + ns[n / 2]) / 2,
+            n => ns[n / 2],
+        };
+        Self { min_ns: ns.first().copied().unwrap_or(0), median_ns: median }
+    }
+}
+
+/// Timing statistics for every stage of one day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DayTiming {
+    pub day: u8,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl DayTiming {
+    pub fn new(day: u8, samples: &Samples) -> Self {
+        Self {
+            day,
+            parse: Stats::new(&samples.parse),
+            part1: Stats::new(&samples.part1),
+            part2: Stats::new(&samples.part2),
+        }
+    }
+    fn total(&self) -> Stats {
+        Stats {
+            min_ns: self.parse.min_ns + self.part1.min_ns + self.part2.min_ns,
+            median_ns: self.parse.median_ns + self.part1.median_ns + self.part2.median_ns,
+        }
+    }
+}
+
+/// The results of one timing run, as exported to JSON.
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub repeat: usize,
+    pub profile: &'static str,
+    pub days: Vec<DayTiming>,
+}
+
+impl Report {
+    pub fn new(repeat: usize, days: Vec<DayTiming>) -> Self {
+        let profile = if cfg!(debug_assertions) { "debug" } else { "release" };
+        Self { repeat, profile, days }
+    }
+
+    pub fn table(&self) -> Table {
+        let mut table = Table::new(&[
+            ("day", Align::Right),
+            ("stage", Align::Left),
+            ("median", Align::Right),
+            ("min", Align::Right),
+        ]);
+        for d in &self.days {
+            for (stage, s) in [("parse", d.parse), ("part 1", d.part1),
+                               ("part 2", d.part2), ("total", d.total())] {
+                table.push(vec![
+                    d.day.to_string(),
+                    stage.to_owned(),
+                    format!("{:.3?}", Duration::from_nanos(s.median_ns)),
+                    format!("{:.3?}", Duration::from_nanos(s.min_ns)),
+                ]);
+            }
+        }
+        table
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ns(v: &[u64]) -> Vec<Duration> { v.iter().map(|&x| Duration::from_nanos(x)).collect() }
+
+    #[test]
+    fn stats() {
+        assert_eq!(Stats::new(&ns(&[5, 1, 3])), Stats { min_ns: 1, median_ns: 3 });
+        assert_eq!(Stats::new(&ns(&[4, 1, 2, 9])), Stats { min_ns: 1, median_ns: 3 });
+        assert_eq!(Stats::new(&[]), Stats { min_ns: 0, median_ns: 0 });
+    }
+
+    #[test]
+    fn report() {
+        let samples = Samples { parse: ns(&[10, 20]), part1: ns(&[1]), part2: ns(&[2]) };
+        let report = Report::new(2, vec![DayTiming::new(4, &samples)]);
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["repeat"], 2);
+        assert_eq!(json["days"][0]["day"], 4);
+        assert_eq!(json["days"][0]["parse"]["median_ns"], 15);
+        let table = report.table().to_string();
+        assert!(table.lines().nth(4).unwrap().contains("total"));
+    }
+}
@@ -0,0 +1,235 @@
+//! Day 1: Trebuchet?!
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use tracing::{debug, trace};
+use utils::{parse_lines, Error, Solution};
+
+#[derive(Debug, Clone, Copy)]
+enum Word {
+    Zero,
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+}
+
+impl TryFrom<String> for Word {
+    type Error = ();
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match &s[..] {
+            "zero"  => Ok(Self::Zero),
+            "one"   => Ok(Self::One),
+            "two"   => Ok(Self::Two),
+            "three" => Ok(Self::Three),
+            "four"  => Ok(Self::Four),
+            "five"  => Ok(Self::Five),
+            "six"   => Ok(Self::Six),
+            "seven" => Ok(Self::Seven),
+            "eight" => Ok(Self::Eight),
+            "nine"  => Ok(Self::Nine),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<Word> for u32 {
+    fn from(w: Word) -> u32 {
+        match w {
+           Word::Zero => 0u32,
+           Word::One  => 1u32,
+           Word::Two  => 2u32,
+           Word::Three=> 3u32,
+           Word::Four => 4u32,
+           Word::Five => 5u32,
+           Word::Six  => 6u32,
+           Word::Seven=> 7u32,
+           Word::Eight=> 8u32,
+           Word::Nine => 9u32,
+        }
+    }
+}
+
+/// A digit of a line, written out or spelled, and the index it starts at.
+#[derive(Debug, Clone, Copy)]
+struct Digit {
+    idx: usize,
+    val: u32,
+}
+
+impl PartialOrd for Digit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.idx.partial_cmp(&other.idx)
+    }
+}
+
+impl PartialEq for Digit {
+    fn eq(&self, other: &Self) -> bool {
+        self.idx == other.idx
+    }
+}
+
+fn is_digit(v: u32) -> bool {
+    (0x30..0x3a).contains(&v)
+}
+
+fn find_first(s: &str) -> Option<Digit> {
+    for (i, c) in s.chars().enumerate() {
+        let v: u32 = c.into();
+        if is_digit(v) { return Some(Digit { idx: i, val: v - 0x30 }) }
+    }
+    None
+}
+
+fn find_last(s: &str) -> Option<Digit> {
+    for (i, c) in s.chars().rev().enumerate() {
+        let v: u32 = c.into();
+        if is_digit(v) { return Some(Digit {
+									idx: s.len() - i - 1,
+									val: v - 0x30 })
+		}
+    }
+    None
+}
+
+fn find_word_indices(s: &str) -> Option<(Digit, Digit)> {
+    fn do_match(i: usize, c: char, len: usize, dup: &[char]) ->
+                Option<Digit> {
+        if let 'o' | 'z' | 't' | 'f' | 's' | 'e' | 'n' = c {
+            for l in 3..6 {
+                if i + l <= len {
+                    let res: Result<Word, ()> = dup[i..i+l]
+                                                    .iter()
+                                                    .collect::<String>()
+                                                    .try_into();
+                    if let Ok(m) = res {
+                        trace!(word = ?m, idx = i, "matched a spelled digit");
+                        let val: u32 = m.into();
+                        return Some(Digit { idx: i, val });
+                    }
+                }
+            };
+        }
+        None
+    }
+    let dup: Vec<char> = s.chars().collect();
+    let len = dup.len();
+    let mut first: Option<Digit> = None;
+    let mut second: Option<Digit> = None;
+    for (i, c) in s.chars().enumerate() {
+        if let Some(c) = do_match(i, c, len, &dup) { first = Some(c); break; }
+    }
+    for (i, c) in s.chars().rev().enumerate() {
+        if let Some(c) = do_match(len - i - 1, c, len, &dup) {
+            second = Some(c);
+            break;
+        }
+    }
+    if first.is_some() || second.is_some() {
+        Some((first.unwrap(), second.unwrap()))
+    }
+    else { None }
+}
+
+/// Combines the first and last digit of `s`. Lines spelling out all their
+/// digits only count once words are read, so they are worth nothing here.
+pub fn get_calibration_value_1(s: &str) -> u32 {
+    let value = match (find_first(s), find_last(s)) {
+        (Some(first), Some(last)) => first.val * 10 + last.val,
+        _ => 0,
+    };
+    debug!(line = s, value, "calibration value");
+    value
+}
+
+/// Like [`get_calibration_value_1`], also reading digits spelled as words.
+pub fn get_calibration_value_2(s: &str) -> u32 {
+    let mut first = find_first(s);
+    let mut last = find_last(s);
+    if let Some(t) = find_word_indices(s) {
+        if first.is_none_or(|f| t.0 < f) { first = Some(t.0) }
+        if last.is_none_or(|l| t.1 > l) { last = Some(t.1) }
+    }
+    let value = match (first, last) {
+        (Some(first), Some(last)) => first.val * 10 + last.val,
+        _ => 0,
+    };
+    debug!(line = s, value, "calibration value");
+    value
+}
+
+/// Reads the calibration document, one line per value.
+pub fn parse(input: &str) -> Result<Vec<String>, Error> {
+    parse_lines(input, |x| {
+        if !x.chars().any(|c| is_digit(c.into()))
+                && find_word_indices(x).is_none() {
+            return Err(Error::at(x, x, "line does not contain a digit"));
+        }
+        Ok(x.to_owned())
+    })
+}
+
+/// Sums the calibration values made of the first and last digit of each line.
+pub fn part1(lines: &[String]) -> u64 {
+    lines.iter().map(|x| get_calibration_value_1(x) as u64).sum()
+}
+
+/// Sums the calibration values when digits may also be spelled out.
+pub fn part2(lines: &[String]) -> u64 {
+    lines.iter().map(|x| get_calibration_value_2(x) as u64).sum()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const DAY: u8 = 1;
+    #[cfg(feature = "embed-inputs")]
+    const EMBEDDED: Option<&'static str> = Some(include_str!("../input.txt"));
+
+    fn parse(input: &str) -> Result<Self::Input, Error> { parse(input) }
+    fn part1(lines: &Self::Input) -> u64 { part1(lines) }
+    fn part2(lines: &Self::Input) -> u64 { part2(lines) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+        assert_eq!(Day1::part1(&Day1::parse(input).unwrap()), 142);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n\
+                     4nineeightseven2\nzoneight234\n7pqrstsixteen";
+        assert_eq!(Day1::part2(&Day1::parse(input).unwrap()), 281);
+    }
+
+    #[test]
+    fn calibration_values() {
+        assert_eq!(get_calibration_value_1("treb7uchet"), 77);
+        assert_eq!(get_calibration_value_1("two"), 0);
+        assert_eq!(get_calibration_value_2("eightwo"), 82);
+        assert_eq!(get_calibration_value_2("oneight"), 18);
+        assert_eq!(get_calibration_value_2("a:b9"), 99);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Day1::parse("two\nthreeight").is_ok());
+        let e = Day1::parse("1abc2\nabc").unwrap_err();
+        assert_eq!(e.to_string(), "2:1: line does not contain a digit");
+    }
+}
@@ -0,0 +1,264 @@
+//! Day 11: Cosmic Expansion
+
+use tracing::{debug, trace};
+use utils::geom::Point;
+#[cfg(feature = "render")]
+use utils::render::{self, Picture, Rgb, Style};
+use utils::{Error, Grid, Solution};
+
+/// A galaxy's position and how many empty columns and rows precede it.
+#[derive(Debug, Clone, Copy)]
+pub struct Galaxy {
+    pos: Point,
+    expansion: Point,
+}
+
+impl Galaxy {
+    fn new(pos: Point) -> Self {
+        Self { pos, expansion: Point::ORIGIN }
+    }
+    pub fn pos(&self) -> Point { self.pos }
+    /// Moves the galaxy as if every empty row and column was `factor` wide.
+    pub fn expand(&mut self, factor: i64) {
+        assert!(factor >= 1);
+        self.pos += self.expansion * (factor - 1);
+    }
+
+    /// The Manhattan distance to `other`.
+    pub fn shortest_path(&self, other: &Self) -> u64 {
+        self.pos.manhattan(other.pos)
+    }
+}
+
+fn sum_paths(g: &[Galaxy]) -> u64 {
+    let mut sum = 0u64;
+
+    for (i, gg) in g.iter().enumerate() {
+        for j in i+1..g.len() {
+            if let Some(other) = g.get(j) { sum += gg.shortest_path(other); }
+        }
+    }
+    sum
+}
+
+/// The telescope image, set where there is a galaxy, and its galaxies.
+#[derive(Debug, Clone)]
+pub struct Image {
+    pixels: Grid<bool>,
+    galaxies: Vec<Galaxy>,
+}
+
+impl Image {
+    pub fn pixels(&self) -> &Grid<bool> { &self.pixels }
+    pub fn galaxies(&self) -> &[Galaxy] { &self.galaxies }
+}
+
+fn empty_rows(pixels: &Grid<bool>) -> Vec<bool> {
+    pixels.rows().map(|r| !r.contains(&true)).collect()
+}
+
+fn empty_cols(pixels: &Grid<bool>) -> Vec<bool> {
+    pixels.columns().map(|mut c| !c.any(|&x| x)).collect()
+}
+
+/// Reads the image, recording the empty rows and columns before each galaxy.
+pub fn parse(input: &str) -> Result<Image, Error> {
+    let pixels = Grid::parse(input, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    let rows = empty_rows(&pixels);
+    let cols = empty_cols(&pixels);
+    let mut g: Vec<Galaxy> = pixels.iter()
+                                  .filter(|x| *x.1)
+                                  .map(|(p, _)| Galaxy::new(Point::from(p)))
+                                  .collect();
+
+    for (c, b) in cols.iter().enumerate() {
+        g.iter_mut().for_each(|x| if *b && x.pos.x > c as i64 { x.expansion.x += 1 });
+    }
+    for (r, b) in rows.iter().enumerate() {
+        g.iter_mut().for_each(|x| if *b && x.pos.y > r as i64 { x.expansion.y += 1 });
+    }
+    Ok(Image { pixels, galaxies: g })
+}
+
+/// Sums the distances between every pair of galaxies once each empty row and
+/// column is `factor` wide.
+pub fn shortest_path(g: &[Galaxy], factor: i64) -> u64 {
+    let mut g = g.to_vec();
+    g.iter_mut().for_each(|x| {
+        let before = x.pos;
+        x.expand(factor);
+        trace!(%before, after = %x.pos, "expanded galaxy");
+    });
+    let sum = sum_paths(&g);
+    debug!(factor, galaxies = g.len(), sum);
+    sum
+}
+
+pub fn part1(image: &Image) -> u64 { shortest_path(&image.galaxies, 2) }
+pub fn part2(image: &Image) -> u64 { shortest_path(&image.galaxies, 1_000_000) }
+
+/// A pixel of the image, telling apart the empty space that expands.
+#[cfg(feature = "render")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pixel {
+    Space,
+    /// A pixel of an empty row or column.
+    Expanding,
+    Galaxy,
+}
+
+#[cfg(feature = "render")]
+fn pixels(image: &Image) -> Grid<Pixel> {
+    let rows = empty_rows(image.pixels());
+    let cols = empty_cols(image.pixels());
+    Grid::from_fn(image.pixels().width(), image.pixels().height(), |x, y| {
+        match image.pixels()[(x, y)] {
+            true => Pixel::Galaxy,
+            false if rows[y] || cols[x] => Pixel::Expanding,
+            false => Pixel::Space,
+        }
+    })
+}
+
+/// The pixels of the image once every empty row and column is `factor` wide.
+#[cfg(feature = "render")]
+fn expanded(image: &Image, factor: usize) -> Grid<Pixel> {
+    // the row or column of the image each row or column of the result shows
+    let sources = |empty: Vec<bool>| -> Vec<usize> {
+        empty.iter()
+             .enumerate()
+             .flat_map(|(i, &e)| std::iter::repeat_n(i, if e { factor } else { 1 }))
+             .collect()
+    };
+    let cols = sources(empty_cols(image.pixels()));
+    let rows = sources(empty_rows(image.pixels()));
+    let pixels = pixels(image);
+    Grid::from_fn(cols.len(), rows.len(), |x, y| pixels[(cols[x], rows[y])])
+}
+
+/// Draws the image with the galaxies in yellow over the empty rows and
+/// columns that expand, in blue.
+#[cfg(feature = "render")]
+pub fn visualize(image: &Image) -> String {
+    let galaxy = Style::fg(Rgb::YELLOW).bold();
+    let space = Style::fg(Rgb::GREY);
+    let expanding = Style::fg(Rgb::GREY).with_bg(Rgb::NAVY);
+    let mut s = render::ansi(&pixels(image), |_, pixel| match pixel {
+        Pixel::Galaxy => ('#', galaxy),
+        Pixel::Expanding => ('.', expanding),
+        Pixel::Space => ('.', space),
+    });
+    s.push_str(&render::legend(&[
+        (galaxy.with_bg(Rgb::YELLOW), "galaxy"),
+        (expanding, "expanding row or column"),
+    ]));
+    s
+}
+
+/// The image before and after the expansion of part 1, coloured like
+/// [`visualize`].
+#[cfg(feature = "render")]
+pub fn picture(image: &Image) -> Picture {
+    let color = |_, pixel: &Pixel| match pixel {
+        Pixel::Galaxy => Rgb::YELLOW,
+        Pixel::Expanding => Rgb::NAVY,
+        Pixel::Space => Rgb::BLACK,
+    };
+    let mut picture = Picture::new(&pixels(image), color);
+    picture.push(&expanded(image, 2), color);
+    picture
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Image;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const DAY: u8 = 11;
+    #[cfg(feature = "embed-inputs")]
+    const EMBEDDED: Option<&'static str> = Some(include_str!("../input.txt"));
+
+    fn parse(input: &str) -> Result<Self::Input, Error> { parse(input) }
+    fn part1(image: &Self::Input) -> u64 { part1(image) }
+    fn part2(image: &Self::Input) -> u64 { part2(image) }
+    #[cfg(feature = "render")]
+    fn visualize(image: &Self::Input) -> Option<String> { Some(visualize(image)) }
+    #[cfg(feature = "render")]
+    fn picture(image: &Self::Input) -> Option<Picture> { Some(picture(image)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day11::part1(&Day11::parse(EXAMPLE).unwrap()), 374);
+    }
+
+    #[test]
+    fn part2_example() {
+        let image = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(shortest_path(image.galaxies(), 10), 1030);
+        assert_eq!(shortest_path(image.galaxies(), 100), 8410);
+    }
+
+    #[test]
+    fn galaxies() {
+        let image = parse("#..\n...\n..#").unwrap();
+        let g = image.galaxies();
+        let positions: Vec<_> = g.iter().map(|x| (x.pos, x.expansion)).collect();
+        assert_eq!(positions, vec![(Point::new(0, 0), Point::new(0, 0)),
+                                   (Point::new(2, 2), Point::new(1, 1))]);
+        assert_eq!(shortest_path(g, 2), 6);
+        assert!(parse("...\n...").unwrap().galaxies().is_empty());
+        assert!(parse("").unwrap().galaxies().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "render")]
+    fn highlights() {
+        let s = visualize(&parse("#..\n...\n..#").unwrap());
+        let galaxy = Style::fg(Rgb::YELLOW).bold().escape();
+        let expanding = Style::fg(Rgb::GREY).with_bg(Rgb::NAVY).escape();
+        let rows: Vec<&str> = s.lines().collect();
+        assert!(rows[0].starts_with(&format!("{galaxy}#")));
+        assert!(rows[0].contains(&format!("{expanding}.")));
+        assert_eq!(rows[1].matches(&expanding).count(), 1);
+        assert_eq!(rows.len(), 4);
+
+        let p = picture(&parse("#..\n...\n..#").unwrap());
+        let sizes: Vec<_> = p.frames().iter().map(|f| (f.width(), f.height())).collect();
+        assert_eq!(sizes, vec![(3, 3), (4, 4)]);
+        let after = &p.frames()[1];
+        assert_eq!((after[(0, 0)], after[(3, 3)]), (Rgb::YELLOW, Rgb::YELLOW));
+        assert!(after.row(1).iter().chain(after.row(2)).all(|&c| c == Rgb::NAVY));
+        assert_eq!(after[(3, 0)], Rgb::BLACK);
+    }
+
+    #[test]
+    fn galaxy_errors() {
+        let e = parse("#..\n.#").unwrap_err();
+        assert_eq!(e.to_string(), "2:3: expected 3 columns, found 2");
+        let e = parse("#..\n.x.").unwrap_err();
+        assert_eq!(e.to_string(), "2:2: invalid character `x`");
+    }
+}
@@ -0,0 +1,139 @@
+//! Day 2: Cube Conundrum
+
+use tracing::debug;
+use utils::{expect_next, parse_lines, parse_token, split_header, Error, Solution};
+
+#[derive(Debug, Clone, Copy)]
+struct Cubes {
+    r: u8,
+    b: u8,
+    g: u8
+}
+
+impl Cubes {
+    fn is_possible(&self, r: u8, b: u8, g: u8) -> bool {
+        self.r <= r && self.b <= b && self.g <= g
+    }
+    fn power(&self) -> u64 {
+        self.r as u64 * self.b as u64 * self.g as u64
+    }
+}
+
+fn parse_cubes(s: &str) -> Result<Cubes, Error> {
+    let mut r: u8 = 0;
+    let mut b: u8 = 0;
+    let mut g: u8 = 0;
+    let items = s.split("; ");
+    for i in items {
+        for ii in i.split(", ") {
+            let mut j = ii.split(' ');
+            let cnt = expect_next(&mut j, ii, "a count").map_err(|e| e.within(s, ii))?;
+            let cnt = parse_token::<u8>(s, cnt)?;
+            match expect_next(&mut j, ii, "a color").map_err(|e| e.within(s, ii))? {
+                "red" => if cnt > r  { r = cnt },
+                "blue" => if cnt > b { b = cnt },
+                "green" => if cnt > g { g = cnt },
+                c => return Err(Error::at(s, c, format!("unknown color `{c}`"))),
+            }
+        }
+    }
+    Ok(Cubes { r, b, g })
+}
+
+/// A game's index and the most cubes of each color shown at once.
+#[derive(Debug, Clone, Copy)]
+pub struct Game {
+    idx: u64,
+    cubes: Cubes,
+}
+
+impl Game {
+    /// Parses a `Game N: 3 blue, 4 red; ...` line.
+    pub fn new(s: &str) -> Result<Self, Error> {
+        let (idx, cubes) = split_header(s, "Game")?;
+        let cubes = parse_cubes(cubes).map_err(|e| e.within(s, cubes))?;
+        Ok(Game { idx, cubes })
+    }
+    pub fn idx(&self) -> u64 { self.idx }
+    /// Whether the game could be played with a bag of `r`, `b` and `g` cubes.
+    pub fn is_possible(&self, r: u8, b: u8, g: u8) -> bool {
+        self.cubes.is_possible(r, b, g)
+    }
+    /// The product of the fewest cubes of each color the game needs.
+    pub fn power(&self) -> u64 { self.cubes.power() }
+}
+
+/// Reads one game per line.
+pub fn parse(input: &str) -> Result<Vec<Game>, Error> {
+    parse_lines(input, Game::new)
+}
+
+/// Sums the indices of the games possible with 12 red, 14 blue and 13 green.
+pub fn part1(games: &[Game]) -> u64 {
+    games.iter()
+         .filter(|x| {
+             let possible = x.is_possible(12, 14, 13);
+             debug!(game = x.idx, cubes = ?x.cubes, possible);
+             possible
+         })
+         .map(|x| x.idx)
+         .sum()
+}
+
+/// Sums the power of every game.
+pub fn part2(games: &[Game]) -> u64 {
+    games.iter()
+         .map(|x| {
+             let power = x.power();
+             debug!(game = x.idx, cubes = ?x.cubes, power);
+             power
+         })
+         .sum()
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Game>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const DAY: u8 = 2;
+    #[cfg(feature = "embed-inputs")]
+    const EMBEDDED: Option<&'static str> = Some(include_str!("../input.txt"));
+
+    fn parse(input: &str) -> Result<Self::Input, Error> { parse(input) }
+    fn part1(games: &Self::Input) -> u64 { part1(games) }
+    fn part2(games: &Self::Input) -> u64 { part2(games) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day2::part1(&Day2::parse(EXAMPLE).unwrap()), 8);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day2::part2(&Day2::parse(EXAMPLE).unwrap()), 2286);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let e = Game::new("Game 1: 3 blue, 4 purple").unwrap_err();
+        assert_eq!(e.to_string(), "1:19: unknown color `purple`");
+        let e = Game::new("Game x: 3 blue").unwrap_err();
+        assert_eq!(e.column, Some(6));
+        assert!(Game::new("Game 1").is_err());
+    }
+}
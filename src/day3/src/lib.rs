@@ -0,0 +1,322 @@
+//! Day 3: Gear Ratios
+
+#[cfg(feature = "render")]
+use std::collections::HashSet;
+use tracing::{debug, trace};
+use utils::geom::Point;
+#[cfg(feature = "render")]
+use utils::render::{self, Picture, Rgb, Style};
+use utils::{Error, Grid, Solution};
+
+/// A number of the schematic spanning `start` to `end` on a single row.
+#[derive(Debug, Clone, Copy)]
+struct PartNumber {
+    start: Point,
+    end: Point,
+    val: u32,
+}
+
+impl PartNumber {
+    fn cells(&self) -> impl Iterator<Item = Point> {
+        let y = self.start.y;
+        (self.start.x..self.end.x + 1).map(move |x| Point::new(x, y))
+    }
+    /// Whether `p` touches the number, diagonally included.
+    fn is_adjacent(&self, p: Point) -> bool {
+        self.cells().any(|c| c.chebyshev(p) == 1)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Gear {
+    coord: Point,
+    borders: u8,
+    border_val: u32,
+}
+
+impl Gear {
+    fn get_borders(&mut self, parts: &[PartNumber]) {
+        parts.iter().filter(|pt| pt.is_adjacent(self.coord)).for_each(|pt| {
+            self.borders += 1;
+            self.border_val *= pt.val;
+        });
+    }
+}
+
+fn is_symbol(c: char) -> bool { !c.is_ascii_digit() && c != '.' }
+
+/// Whether any cell around `pt` is set in `symbols`.
+fn borders(symbols: &Grid<bool>, pt: &PartNumber) -> bool {
+    pt.cells().any(|c| c.neighbours8().any(|p| symbols.at(p) == Some(&true)))
+}
+
+fn get_part_numbers(s: &[char], row: i64) -> Vec<PartNumber> {
+    let mut v: Vec<PartNumber> = Vec::new();
+    let len = s.len() as i64;
+    let mut val = 0;
+    let mut end = 0i64;
+    let mut pow = 0;
+    for (i, c) in s.iter().rev().enumerate() {
+        match c.to_digit(10) {
+            Some(d) => {
+                val += d * (10u32.pow(pow));
+                if end == 0 { end = len - (i as i64) - 1};
+                pow+= 1
+            },
+            None => {
+                if pow != 0 {
+                    v.push(PartNumber {
+                             start: Point::new(len - i as i64, row),
+                             end:   Point::new(end, row),
+                             val
+                    });
+                    val = 0; end = 0; pow = 0;
+                }
+            },
+        }
+    }
+    // handle edge case
+    if pow != 0 {
+        v.push(PartNumber {
+                 start: Point::new(0, row),
+                 end:   Point::new(end, row),
+                 val
+        });
+    }
+    v
+}
+
+fn get_symbols(data: &Grid<char>, stars_only: bool) -> Grid<bool> {
+    data.map(|&c| if stars_only { c == '*' } else { is_symbol(c) })
+}
+
+pub fn find_adjacent_parts(data: &Grid<char>) -> u32 {
+    let grid = get_symbols(data, false);
+    let mut sum = 0u32;
+    for (i, x) in data.rows().enumerate() {
+        for pt in get_part_numbers(x, i as i64) {
+            if borders(&grid, &pt) {
+                debug!(value = pt.val, at = %pt.start, "part number");
+                sum += pt.val;
+            } else {
+                trace!(value = pt.val, at = %pt.start, "next to no symbol");
+            }
+        }
+    }
+    sum
+}
+
+pub fn find_adjacent_gears(data: &Grid<char>) -> u32 {
+    let grid = get_symbols(data, true);
+    let mut gears: Vec<Gear> = grid.iter()
+                                   .filter(|x| *x.1)
+                                   .map(|(p, _)| Gear {
+                                        coord: Point::from(p),
+                                        borders: 0,
+                                        border_val: 1,
+                                   })
+                                   .collect();
+    let mut v: Vec<PartNumber> = Vec::new();
+    for (i, x) in data.rows().enumerate() {
+        v.extend(get_part_numbers(x, i as i64));
+    }
+    gears.iter_mut().for_each(|x| x.get_borders(&v));
+    gears.iter().fold(0u32, |sum, x| {
+        if x.borders == 2 {
+            debug!(at = %x.coord, ratio = x.border_val, "gear");
+            sum + x.border_val
+        }
+        else {
+            trace!(at = %x.coord, numbers = x.borders, "not a gear");
+            sum
+        }
+    })
+}
+
+/// What a cell of the schematic contributes to the answers.
+#[cfg(feature = "render")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    Empty,
+    /// A digit of a number next to no symbol.
+    Number,
+    /// A digit of a part number counted in part 1.
+    Part,
+    Symbol,
+    /// A `*` next to exactly two part numbers, counted in part 2.
+    Gear,
+}
+
+#[cfg(feature = "render")]
+fn marks(schematic: &Grid<char>) -> Grid<Mark> {
+    let symbols = get_symbols(schematic, false);
+    let parts: Vec<PartNumber> = schematic.rows()
+                                          .enumerate()
+                                          .flat_map(|(i, x)| get_part_numbers(x, i as i64))
+                                          .collect();
+    let counted: HashSet<Point> = parts.iter()
+                                       .filter(|pt| borders(&symbols, pt))
+                                       .flat_map(|pt| pt.cells())
+                                       .collect();
+    let is_gear = |p: Point| parts.iter().filter(|pt| pt.is_adjacent(p)).count() == 2;
+    Grid::from_fn(schematic.width(), schematic.height(), |x, y| {
+        let p = Point::from((x, y));
+        match schematic[(x, y)] {
+            '*' if is_gear(p) => Mark::Gear,
+            _ if counted.contains(&p) => Mark::Part,
+            c if c.is_ascii_digit() => Mark::Number,
+            c if is_symbol(c) => Mark::Symbol,
+            _ => Mark::Empty,
+        }
+    })
+}
+
+/// Draws the schematic with the part numbers counted in part 1 in green, the
+/// gears of part 2 in yellow and the numbers next to no symbol greyed out.
+#[cfg(feature = "render")]
+pub fn visualize(schematic: &Grid<char>) -> String {
+    let part = Style::fg(Rgb::GREEN).bold();
+    let gear = Style::fg(Rgb::BLACK).with_bg(Rgb::YELLOW).bold();
+    let symbol = Style::fg(Rgb::RED);
+    let faded = Style::fg(Rgb::GREY);
+    let marks = marks(schematic);
+    let mut s = render::ansi(schematic, |p, &c| {
+        let style = match marks[p] {
+            Mark::Gear => gear,
+            Mark::Part => part,
+            Mark::Symbol => symbol,
+            Mark::Number | Mark::Empty => faded,
+        };
+        (c, style)
+    });
+    s.push_str(&render::legend(&[
+        (part.with_bg(Rgb::GREEN), "part number"),
+        (gear, "gear"),
+        (symbol.with_bg(Rgb::RED), "symbol"),
+    ]));
+    s
+}
+
+/// Colours the schematic like [`visualize`], one cell per character.
+#[cfg(feature = "render")]
+pub fn picture(schematic: &Grid<char>) -> Picture {
+    Picture::new(&marks(schematic), |_, mark| match mark {
+        Mark::Empty => Rgb::BLACK,
+        Mark::Number => Rgb::GREY,
+        Mark::Part => Rgb::GREEN,
+        Mark::Symbol => Rgb::RED,
+        Mark::Gear => Rgb::YELLOW,
+    })
+}
+
+/// Reads the engine schematic, checking that every row has the same width.
+pub fn parse(input: &str) -> Result<Grid<char>, Error> {
+    let grid = Grid::parse(input, Some)?;
+    if grid.is_empty() { return Err(Error::new("empty schematic")) }
+    Ok(grid)
+}
+
+/// Sums the part numbers adjacent to any symbol.
+pub fn part1(schematic: &Grid<char>) -> u32 { find_adjacent_parts(schematic) }
+/// Sums the gear ratios of every `*` adjacent to exactly two part numbers.
+pub fn part2(schematic: &Grid<char>) -> u32 { find_adjacent_gears(schematic) }
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Grid<char>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    const DAY: u8 = 3;
+    #[cfg(feature = "embed-inputs")]
+    const EMBEDDED: Option<&'static str> = Some(include_str!("../input.txt"));
+
+    fn parse(input: &str) -> Result<Self::Input, Error> { parse(input) }
+    fn part1(schematic: &Self::Input) -> u32 { part1(schematic) }
+    fn part2(schematic: &Self::Input) -> u32 { part2(schematic) }
+    #[cfg(feature = "render")]
+    fn visualize(schematic: &Self::Input) -> Option<String> { Some(visualize(schematic)) }
+    #[cfg(feature = "render")]
+    fn picture(schematic: &Self::Input) -> Option<Picture> { Some(picture(schematic)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day3::part1(&Day3::parse(EXAMPLE).unwrap()), 4361);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day3::part2(&Day3::parse(EXAMPLE).unwrap()), 467835);
+    }
+
+    #[test]
+    fn non_square() {
+        let schematic = parse("2.....\n.*3...").unwrap();
+        assert_eq!((part1(&schematic), part2(&schematic)), (5, 6));
+    }
+
+    fn spans(s: &str) -> Vec<(i64, i64, u32)> {
+        let s: Vec<char> = s.chars().collect();
+        let mut v: Vec<(i64, i64, u32)> = get_part_numbers(&s, 4)
+            .iter()
+            .inspect(|pt| assert_eq!((pt.start.y, pt.end.y), (4, 4)))
+            .map(|pt| (pt.start.x, pt.end.x, pt.val))
+            .collect();
+        v.sort();
+        v
+    }
+
+    #[test]
+    fn part_numbers() {
+        assert_eq!(spans("467..114.."), vec![(0, 2, 467), (5, 7, 114)]);
+        assert_eq!(spans("..35"), vec![(2, 3, 35)]);
+        assert_eq!(spans("12*34"), vec![(0, 1, 12), (3, 4, 34)]);
+        assert_eq!(spans("0.7"), vec![(0, 0, 0), (2, 2, 7)]);
+        assert_eq!(spans("..*."), vec![]);
+        assert_eq!(spans(""), vec![]);
+    }
+
+    #[test]
+    #[cfg(feature = "render")]
+    fn highlights() {
+        let s = visualize(&parse("12.\n.*.\n3..").unwrap());
+        let part = Style::fg(Rgb::GREEN).bold().escape();
+        let gear = Style::fg(Rgb::BLACK).with_bg(Rgb::YELLOW).bold().escape();
+        let rows: Vec<&str> = s.lines().collect();
+        assert!(rows[0].starts_with(&format!("{part}12")));
+        assert!(rows[1].contains(&format!("{gear}*")));
+        assert!(rows[2].starts_with(&format!("{part}3")));
+        assert_eq!(rows.len(), 4);
+
+        let p = picture(&parse("12.\n.*.\n3.4").unwrap());
+        let colors: Vec<Rgb> = p.frames()[0].iter().map(|x| *x.1).collect();
+        assert_eq!(colors, [Rgb::GREEN, Rgb::GREEN, Rgb::BLACK,
+                            Rgb::BLACK, Rgb::RED, Rgb::BLACK,
+                            Rgb::GREEN, Rgb::BLACK, Rgb::GREEN]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let e = Day3::parse("12.\n4").unwrap_err();
+        assert_eq!(e.to_string(), "2:2: expected 3 columns, found 1");
+        assert!(Day3::parse("").is_err());
+    }
+}
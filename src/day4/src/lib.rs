@@ -0,0 +1,165 @@
+//! Day 4: Scratchcards
+
+use std::collections::HashMap;
+use tracing::debug;
+use utils::{parse_lines, split_header, values, Error, Solution};
+
+#[derive(Debug, Clone)]
+struct CardCopy {
+    idx: u32,
+    copies: u32,
+    overlaps: u32,
+}
+
+/// A scratchcard and how many of its numbers are winning ones.
+#[derive(Debug, Clone)]
+pub struct Card {
+    winning_numbers: Vec<u8>,
+    numbers: Vec<u8>,
+    idx: u32,
+    points: u32,
+    overlaps: u32,
+}
+
+impl Card {
+    /// Parses a `Card N: winning | numbers` line.
+    pub fn new(s: &str) -> Result<Self, Error> {
+        let (idx, rest) = split_header::<u32>(s, "Card")?;
+        let Some((winning, numbers)) = rest.split_once('|') else {
+            let end = &s[s.len()..];
+            return Err(Error::at(s, end, "expected `|` and the numbers you have"));
+        };
+        let winning = values(winning).map_err(|e| e.within(s, winning))?;
+        let numbers = values(numbers).map_err(|e| e.within(s, numbers))?;
+        let mut card = Self {
+            winning_numbers: winning,
+            numbers,
+            idx,
+            points: 0,
+            overlaps: 0,
+        };
+        card.calculate_points();
+        Ok(card)
+    }
+    pub fn idx(&self) -> u32 { self.idx }
+    /// 1 point for the first winning number, doubled for every further one.
+    pub fn points(&self) -> u32 { self.points }
+    /// How many of the numbers are winning numbers.
+    pub fn overlaps(&self) -> u32 { self.overlaps }
+    fn calculate_points(&mut self) -> u32 {
+        let cnt = self.numbers.iter()
+                              .filter(|x| self.winning_numbers.contains(x))
+                              .count();
+        self.overlaps = cnt as u32;
+        if cnt == 0 { return 0 }
+        let points = 2u32.pow((cnt - 1) as u32);
+        self.points = points;
+        points
+    }
+}
+
+pub fn get_multiplying_card_points(cards: &[Card]) -> u32 {
+    let mut clones: HashMap<u32, CardCopy> = HashMap::new();
+    cards.iter().for_each(|c| {
+        clones.insert(c.idx, CardCopy {
+            idx: c.idx,
+            overlaps: c.overlaps,
+            copies: 1,
+        });
+    });
+
+    let mut sum = 0u32;
+    let mut idx = 1u32;
+    while let Some(c) = clones.remove(&idx) {
+        debug!(card = c.idx, copies = c.copies, wins = c.overlaps);
+        sum += c.copies;
+        for i in 1..c.overlaps + 1 {
+            let idx = c.idx + i;
+            if let Some(cc) = clones.get_mut(&idx) { cc.copies += c.copies }
+        }
+        idx += 1;
+    }
+    sum
+}
+
+/// Reads one card per line.
+pub fn parse(input: &str) -> Result<Vec<Card>, Error> {
+    parse_lines(input, Card::new)
+}
+
+/// Sums the points of every card.
+pub fn part1(cards: &[Card]) -> u64 {
+    cards.iter()
+         .map(|x| {
+             debug!(card = x.idx, matches = x.overlaps, points = x.points);
+             x.points as u64
+         })
+         .sum()
+}
+
+/// Counts the cards held once winning cards copy the ones after them.
+pub fn part2(cards: &[Card]) -> u64 {
+    get_multiplying_card_points(cards) as u64
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Card>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const DAY: u8 = 4;
+    #[cfg(feature = "embed-inputs")]
+    const EMBEDDED: Option<&'static str> = Some(include_str!("../input.txt"));
+
+    fn parse(input: &str) -> Result<Self::Input, Error> { parse(input) }
+    fn part1(cards: &Self::Input) -> u64 { part1(cards) }
+    fn part2(cards: &Self::Input) -> u64 { part2(cards) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day4::part1(&Day4::parse(EXAMPLE).unwrap()), 13);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day4::part2(&Day4::parse(EXAMPLE).unwrap()), 30);
+    }
+
+    #[test]
+    fn card() {
+        let c = Card::new("Card   12:  1  2 | 2  1 3").unwrap();
+        assert_eq!(c.idx(), 12);
+        assert_eq!(c.winning_numbers, vec![1, 2]);
+        assert_eq!(c.numbers, vec![2, 1, 3]);
+        assert_eq!((c.points(), c.overlaps()), (2, 2));
+        let c = Card::new("Card 1: 5 | ").unwrap();
+        assert_eq!((c.points(), c.overlaps()), (0, 0));
+    }
+
+    #[test]
+    fn card_errors() {
+        let e = Card::new("Card 1: 41 48").unwrap_err();
+        assert_eq!(e.to_string(), "1:14: expected `|` and the numbers you have");
+        let e = Card::new("Card x: 41 | 48").unwrap_err();
+        assert_eq!(e.to_string(), "1:6: invalid value `x`: invalid digit found in string");
+        let e = Card::new("Card 1: 41 | 256").unwrap_err();
+        assert_eq!(e.column, Some(14));
+        assert!(Card::new("Card 1 41 | 48").is_err());
+        assert!(Card::new("Card: 41 | 48").is_err());
+    }
+}
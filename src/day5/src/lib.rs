@@ -0,0 +1,239 @@
+//! Day 5: If You Give A Seed A Fertilizer
+
+use std::ops::Range;
+use tracing::{debug, trace, trace_span, warn};
+use utils::ranges::{RangeMap, RangeSet};
+use utils::{expect_next, record, sections, split_label, values, Error, Solution};
+
+/// One section of the almanac, mapping source numbers to destinations.
+#[derive(Debug, Default)]
+pub struct Map(RangeMap<u64>);
+
+impl Map {
+    /// The destination of `input`, which is unchanged if no range covers it.
+    pub fn get_output(&self, input: u64) -> u64 {
+        let output = self.0.get(input);
+        trace!(input, output, range = ?self.0.entry(input).map(|e| e.0));
+        output
+    }
+    /// The destinations of every number in `inputs`.
+    pub fn get_outputs(&self, inputs: &RangeSet<u64>) -> RangeSet<u64> {
+        let outputs = self.0.map_set(inputs);
+        trace!(inputs = ?inputs.ranges(), outputs = ?outputs.ranges());
+        outputs
+    }
+}
+
+/// Parses a `name map:` section with a `destination source length` line per
+/// range.
+fn get_map(s: &str, name: &str) -> Result<Map, Error> {
+    let mut map = Map::default();
+    for line in split_label(s, &format!("{name} map"))?.lines() {
+        let [dest, src, range] = record::<3, u64>(line).map_err(|e| e.within(s, line))?;
+        let end = src.checked_add(range).ok_or_else(|| {
+            Error::at(s, line, "source range ends past the largest number")
+        })?;
+        map.0.insert(src..end, dest).map_err(|r| {
+            Error::at(s, line, format!("source range overlaps {}..{}", r.start, r.end))
+        })?;
+    }
+    Ok(map)
+}
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+fn get_maps<'a>(input: &'a str, mut sections: impl Iterator<Item = &'a str>) ->
+                Result<Vec<Map>, Error> {
+    MAPS.iter().map(|name| {
+        let section = expect_next(&mut sections, input, &format!("a {name} map"))?;
+        get_map(section, name).map_err(|e| e.within(input, section))
+    }).collect()
+}
+
+/// The ranges of seeds of part 2, each a pair of a start and a length in
+/// the `seeds` read from `list`. A last seed without a length is left out.
+fn get_seed_ranges(list: &str, seeds: &[u64]) -> Result<Vec<Range<u64>>, Error> {
+    let pairs = seeds.chunks_exact(2);
+    if let [seed] = pairs.remainder() { warn!(seed, "seed without a length") }
+    let lengths = list.split_whitespace().skip(1).step_by(2);
+    pairs.zip(lengths).map(|(x, length)| {
+        x[0].checked_add(x[1])
+            .map(|end| x[0]..end)
+            .ok_or_else(|| Error::at(list, length, "seed range ends past the largest number"))
+    }).collect()
+}
+
+/// The seeds to plant, also read as ranges for part 2, and the maps from
+/// seed to location, in order.
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<u64>,
+    seed_ranges: Vec<Range<u64>>,
+    maps: Vec<Map>,
+}
+
+impl Almanac {
+    pub fn seeds(&self) -> &[u64] { &self.seeds }
+    pub fn seed_ranges(&self) -> &[Range<u64>] { &self.seed_ranges }
+    pub fn maps(&self) -> &[Map] { &self.maps }
+    /// Follows `seed` through every map to its location.
+    pub fn location(&self, seed: u64) -> u64 {
+        self.maps.iter().zip(MAPS).fold(seed, |x, (m, name)| {
+            let _map = trace_span!("map", name).entered();
+            m.get_output(x)
+        })
+    }
+}
+
+/// Reads the seeds and the seven maps, each in its own paragraph.
+pub fn parse(s: &str) -> Result<Almanac, Error> {
+    let mut paragraphs = sections(s).into_iter();
+    let seeds = expect_next(&mut paragraphs, s, "a list of seeds")?;
+    let list = split_label(seeds, "seeds").map_err(|e| e.within(s, seeds))?;
+    let seeds: Vec<u64> = values(list).map_err(|e| e.within(s, list))?;
+    let seed_ranges = get_seed_ranges(list, &seeds).map_err(|e| e.within(s, list))?;
+    let maps = get_maps(s, paragraphs)?;
+    Ok(Almanac { seeds, seed_ranges, maps })
+}
+
+/// The lowest location of any of the listed seeds.
+pub fn part1(almanac: &Almanac) -> u64 {
+    let mut min = 0xffff_ffff_ffff_ffffu64;
+    for i in &almanac.seeds {
+        let output = almanac.location(*i);
+        debug!(seed = i, location = output);
+        if output < min { min = output; }
+    }
+    min
+}
+
+/// The lowest location of any seed when the seeds list ranges of them.
+pub fn part2(almanac: &Almanac) -> u64 {
+    let seeds: RangeSet<u64> = almanac.seed_ranges.iter().cloned().collect();
+    almanac.maps
+           .iter()
+           .zip(MAPS)
+           .fold(seeds, |set, (m, name)| {
+               let set = trace_span!("map", name).in_scope(|| m.get_outputs(&set));
+               debug!(map = name, ranges = set.ranges().len(), min = ?set.min());
+               set
+           })
+           .min()
+           .unwrap_or(u64::MAX)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Almanac;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const DAY: u8 = 5;
+    #[cfg(feature = "embed-inputs")]
+    const EMBEDDED: Option<&'static str> = Some(include_str!("../input.txt"));
+
+    fn parse(input: &str) -> Result<Self::Input, Error> { parse(input) }
+    fn part1(almanac: &Self::Input) -> u64 { part1(almanac) }
+    fn part2(almanac: &Self::Input) -> u64 { part2(almanac) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day5::part1(&Day5::parse(EXAMPLE).unwrap()), 35);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day5::part2(&Day5::parse(EXAMPLE).unwrap()), 46);
+    }
+
+    #[test]
+    fn odd_seeds() {
+        let almanac = parse(&EXAMPLE.replace("79 14 55 13", "79 14 55")).unwrap();
+        assert_eq!(almanac.seed_ranges(), &[Range { start: 79, end: 93 }]);
+        assert_eq!((part1(&almanac), part2(&almanac)), (43, 46));
+    }
+
+    #[test]
+    fn map() {
+        let map = get_map("seed-to-soil map:\n50 98 2\n52 50 48", "seed-to-soil").unwrap();
+        let outputs: Vec<u64> = [0, 49, 50, 97, 98, 99, 100]
+            .iter()
+            .map(|&x| map.get_output(x))
+            .collect();
+        assert_eq!(outputs, vec![0, 49, 52, 99, 50, 51, 100]);
+        let seeds: RangeSet<u64> = [45..52, 97..101].into_iter().collect();
+        assert_eq!(map.get_outputs(&seeds).ranges(), &[45..54, 99..101]);
+        let empty = get_map("seed-to-soil map:", "seed-to-soil").unwrap();
+        assert_eq!(empty.get_output(7), 7);
+    }
+
+    #[test]
+    fn map_errors() {
+        let section = "seed-to-soil map:\n50 98\n52 50 48";
+        let e = get_map(section, "seed-to-soil").unwrap_err();
+        assert_eq!(e.to_string(), "2:6: expected 3 values, found 2");
+        let section = "seed-to-soil map:\n50 -98 2";
+        let e = get_map(section, "seed-to-soil").unwrap_err();
+        assert_eq!((e.line, e.column), (Some(2), Some(4)));
+        let e = get_map("seed-to-soil map:\n50 98 2\n0 99 5", "seed-to-soil").unwrap_err();
+        assert_eq!(e.to_string(), "3:1: source range overlaps 98..100");
+        let e = get_map(section, "soil-to-fertilizer").unwrap_err();
+        assert_eq!(e.to_string(), "1:1: expected `soil-to-fertilizer map:`");
+        let e = get_map("seed-to-soil map:\n0 18446744073709551615 2", "seed-to-soil").unwrap_err();
+        assert_eq!(e.to_string(), "2:1: source range ends past the largest number");
+        let e = parse("seeds: 18446744073709551615 1\n\nseed-to-soil map:").unwrap_err();
+        assert_eq!(e.to_string(), "1:29: seed range ends past the largest number");
+        let e = parse("seeds: 1 2\n\nseed-to-soil map:\n1 2 3").unwrap_err();
+        assert_eq!(e.message, "expected a soil-to-fertilizer map");
+    }
+}
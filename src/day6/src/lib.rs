@@ -0,0 +1,148 @@
+//! Day 6: Wait For It
+
+use tracing::debug;
+use utils::math::isqrt_u128;
+use utils::{expect_next, split_label, values, Error, Solution};
+
+/// A race's duration and the record distance to beat.
+#[derive(Debug, Clone, Copy)]
+pub struct Race {
+    time: u64,
+    distance: u64
+}
+
+impl Race {
+    pub fn new(time: u64, distance: u64) -> Self { Self { time, distance } }
+    /// How many ways of holding the button beat the record.
+    pub fn count_winning_options(&self) -> u64 {
+        let (t, d) = (self.time as u128, self.distance as u128);
+        // holding the button for `i` ms leaves the boat at speed `i`, so the
+        // winning holds are the integers between the roots of i * (t - i) = d,
+        // symmetric around t / 2
+        let wins = |i: u128| i * (t - i) > d;
+        if !wins(t / 2) { return 0 }
+        let mut lo = (t - isqrt_u128(t * t - 4 * d)) / 2;
+        while !wins(lo) { lo += 1 }
+        while lo > 0 && wins(lo - 1) { lo -= 1 }
+        (t - 2 * lo + 1) as u64
+    }
+}
+
+/// Reads the values as a single number, ignoring the spaces between them.
+fn get_vec_folded(vals: &str) -> Result<Vec<u64>, Error> {
+    let val: String = vals.split_whitespace().collect();
+    let val = val.parse::<u64>().map_err(|e| {
+        Error::at(vals, vals, format!("invalid value `{val}`: {e}"))
+    })?;
+    Ok(vec![val])
+}
+
+fn get_data(input: &str, folded: bool) -> Result<Vec<Race>, Error> {
+    let mut lines = input.lines();
+    let time = expect_next(&mut lines, input, "a line of times")?;
+    let distance = expect_next(&mut lines, input, "a line of distances")?;
+    let get = |line: &str, label: &str| {
+        let vals = split_label(line, label)?;
+        let v = if folded { get_vec_folded(vals) } else { values(vals) };
+        v.map_err(|e| e.within(line, vals))
+    };
+    let times = get(time, "Time").map_err(|e| e.within(input, time))?;
+    let distances = get(distance, "Distance").map_err(|e| e.within(input, distance))?;
+    if times.len() != distances.len() {
+        return Err(Error::at(input, distance, format!(
+            "expected {} distances, found {}", times.len(), distances.len())));
+    }
+    Ok(times.iter()
+            .zip(distances)
+            .map(|(&time, distance)| Race { time, distance })
+            .collect())
+}
+
+fn find_winners(races: &[Race]) -> u64 {
+    races.iter().fold(1u64, |sum, x| {
+        let wins = x.count_winning_options();
+        debug!(time = x.time, distance = x.distance, wins);
+        sum * wins
+    })
+}
+
+/// The races of the sheet, read both as separate races and as one.
+#[derive(Debug, Clone)]
+pub struct Races {
+    races: Vec<Race>,
+    folded: Vec<Race>,
+}
+
+impl Races {
+    /// The races as listed, one per column.
+    pub fn races(&self) -> &[Race] { &self.races }
+    /// The single race read with the spaces between digits ignored.
+    pub fn folded(&self) -> &[Race] { &self.folded }
+}
+
+/// Reads the `Time:` and `Distance:` lines.
+pub fn parse(input: &str) -> Result<Races, Error> {
+    Ok(Races { races: get_data(input, false)?, folded: get_data(input, true)? })
+}
+
+/// Multiplies the number of ways to win each race.
+pub fn part1(races: &Races) -> u64 { find_winners(&races.races) }
+/// The number of ways to win the single race.
+pub fn part2(races: &Races) -> u64 { find_winners(&races.folded) }
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Races;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const DAY: u8 = 6;
+    #[cfg(feature = "embed-inputs")]
+    const EMBEDDED: Option<&'static str> = Some(include_str!("../input.txt"));
+
+    fn parse(input: &str) -> Result<Self::Input, Error> { parse(input) }
+    fn part1(races: &Self::Input) -> u64 { part1(races) }
+    fn part2(races: &Self::Input) -> u64 { part2(races) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Time:      7  15   30\nDistance:  9  40  200";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day6::part1(&Day6::parse(EXAMPLE).unwrap()), 288);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day6::part2(&Day6::parse(EXAMPLE).unwrap()), 71503);
+    }
+
+    #[test]
+    fn winning_options() {
+        let count = |time, distance| Race::new(time, distance).count_winning_options();
+        assert_eq!(count(7, 9), 4);
+        assert_eq!(count(30, 200), 9);
+        assert_eq!(count(4, 4), 0);
+        assert_eq!(count(0, 0), 0);
+        for (t, d) in (0..40).flat_map(|t| (0..400).map(move |d| (t, d))) {
+            assert_eq!(count(t, d), (0..t).filter(|i| i * (t - i) > d).count() as u64);
+        }
+        assert_eq!(count(u64::MAX, u64::MAX), u64::MAX - 3);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let e = Day6::parse("Time: 7 15\nDistance: 9").unwrap_err();
+        assert_eq!(e.to_string(), "2:1: expected 2 distances, found 1");
+        let e = Day6::parse("Time: 7 x\nDistance: 9 9").unwrap_err();
+        assert_eq!(e.to_string(), "1:9: invalid value `x`: invalid digit found in string");
+        assert!(Day6::parse("Time: 7").is_err());
+        let e = Day6::parse("Time: 7\nDist: 9").unwrap_err();
+        assert_eq!(e.to_string(), "2:1: expected `Distance:`");
+    }
+}
@@ -0,0 +1,307 @@
+//! Day 7: Camel Cards
+
+use std::cmp::Ordering;
+use tracing::{debug, trace};
+use utils::{expect_next, parse_lines, parse_token, Error, Solution};
+
+#[derive(Clone, Copy, Debug)]
+enum Card {
+	Joker,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl TryFrom<char> for Card {
+    type Error = char;
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Ok(match c {
+			'1' =>  Card::Joker,
+            '2' =>  Card::Two,
+            '3' =>  Card::Three,
+            '4' =>  Card::Four,
+            '5' =>  Card::Five,
+            '6' =>  Card::Six,
+            '7' =>  Card::Seven,
+            '8' =>  Card::Eight,
+            '9' =>  Card::Nine,
+            'T' =>  Card::Ten,
+            'J' =>  Card::Jack,
+            'Q' =>  Card::Queen,
+            'K' =>  Card::King,
+            'A' =>  Card::Ace,
+            _ => return Err(c),
+        })
+    }
+}
+
+impl From<Card> for u8 {
+    fn from(c: Card) -> u8 {
+        match c {
+			Card::Joker => 1,
+            Card::Two   => 2,
+            Card::Three => 3,
+            Card::Four  => 4,
+            Card::Five  => 5,
+            Card::Six   => 6,
+            Card::Seven => 7,
+            Card::Eight => 8,
+            Card::Nine  => 9,
+            Card::Ten   => 10,
+            Card::Jack  => 11,
+            Card::Queen => 12,
+            Card::King  => 13,
+            Card::Ace   => 14,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Outcome {
+    HighCard,
+    Pair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl From<Outcome> for u8 {
+    fn from(o: Outcome) -> u8 {
+        match o {
+            Outcome::HighCard     => 1,
+            Outcome::Pair         => 2,
+            Outcome::TwoPair      => 3,
+            Outcome::ThreeOfAKind => 4,
+            Outcome::FullHouse    => 5,
+            Outcome::FourOfAKind  => 6,
+            Outcome::FiveOfAKind  => 7,
+        }
+    }
+}
+
+impl Ord for Outcome {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Into::<u8>::into(*self).cmp(&Into::<u8>::into(*other))
+    }
+}
+
+impl PartialOrd for Outcome {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<(&[Card; 5], bool)> for Outcome {
+    fn from(t: (&[Card; 5], bool)) -> Self {
+		let cards = t.0;
+		let jokers = t.1;
+        let mut v = [0u8; 14];
+        cards.iter().for_each(|x| v[(Into::<u8>::into(*x) - 1) as usize] += 1);
+		let mut count_jokers = 0;
+		if jokers {
+			count_jokers = v[0];
+			v[0] = 0;
+		}
+		let stats: Vec<&u8> = v.iter().filter(|x| **x != 0).collect();
+        let max = match stats.iter().max() {
+			None => 0,
+			Some(x) => **x,
+
+		};
+        let min = match stats.iter().min() {
+			None => 0,
+			Some(x) => **x,
+		};
+        let cnt = stats.iter().fold(0u8, | c, x | if **x != 0 { c + 1 } else { c });
+        let mut outcome = match max {
+            5 => Outcome::FiveOfAKind,
+            4 => Outcome::FourOfAKind,
+            3 => {
+                if min == 2 { Outcome::FullHouse }
+                else { Outcome::ThreeOfAKind }
+            },
+            2 => if count_jokers == 0 {
+					if cnt == 3 { Outcome::TwoPair } else { Outcome::Pair }
+				} else {
+					if cnt == 2 { Outcome::TwoPair } else { Outcome::Pair }
+			},
+            1 => Outcome::HighCard,
+			0 => Outcome::HighCard,
+            _ => panic!("Error processing outcome of {max}"),
+        };
+		if !jokers { return outcome }
+		for _ in 0..count_jokers {
+			outcome = match outcome {
+				Outcome::HighCard => 	 Outcome::Pair,
+				Outcome::Pair     => 	 Outcome::ThreeOfAKind,
+				Outcome::TwoPair  => 	 Outcome::FullHouse,
+				Outcome::ThreeOfAKind => Outcome::FourOfAKind,
+				Outcome::FullHouse => 	 Outcome::FourOfAKind,
+				Outcome::FourOfAKind =>  Outcome::FiveOfAKind,
+				Outcome::FiveOfAKind =>  Outcome::FiveOfAKind,
+			}
+		}
+		outcome
+    }
+}
+
+/// A hand of five cards, its bid and its type.
+#[derive(Clone, Copy, Debug)]
+pub struct Hand {
+    cards: [Card; 5],
+    bid: u64,
+    outcome: Outcome,
+}
+
+impl Hand {
+    /// Parses a `32T3K 765` line. With `jokers`, `1` cards are wild.
+    pub fn new(s: &str, jokers: bool) -> Result<Self, Error> {
+        let mut split = s.split(' ');
+        let hand = expect_next(&mut split, s, "a hand")?;
+        let bid = parse_token::<u64>(s, expect_next(&mut split, s, "a bid")?)?;
+        if hand.chars().count() != 5 {
+            return Err(Error::at(s, hand, format!("expected 5 cards, found `{hand}`")));
+        }
+        let mut cards: [Card; 5] = [Card::Two; 5];
+        for (i, (j, c)) in hand.char_indices().enumerate() {
+            cards[i] = Card::try_from(c).map_err(|c| {
+                Error::at(s, &hand[j..], format!("invalid card `{c}`"))
+            })?;
+        }
+        let outcome = Outcome::from((&cards, jokers));
+        Ok(Self { cards, bid, outcome })
+    }
+    pub fn bid(&self) -> u64 { self.bid }
+    /// The same hand with every jack played as a joker.
+    pub fn with_jokers(&self) -> Self {
+        let mut cards = self.cards;
+        cards.iter_mut().for_each(|x| if let Card::Jack = x { *x = Card::Joker });
+        let outcome = Outcome::from((&cards, true));
+        trace!(cards = ?self.cards, before = ?self.outcome, after = ?outcome, "jokers");
+        Self { cards, bid: self.bid, outcome }
+    }
+    fn is_stronger(&self, other: &Self) -> bool {
+        for i in 0..5 {
+            let val: u8 = self.cards[i].into();
+            let other_val: u8 = other.cards[i].into();
+            if val != other_val { return val > other_val }
+        }
+        true
+    }
+}
+
+/// Sums each bid multiplied by the rank of its hand, weakest first.
+pub fn get_winnings(hands: &[Hand]) -> u64 {
+    let mut sorted: Vec<Hand> = Vec::new();
+    for &hand in hands {
+		let mut inserted: bool = false;
+        for j in 0..sorted.len() {
+            let cmp = sorted[j];
+            match hand.outcome.cmp(&cmp.outcome) {
+				Ordering::Less => {
+					sorted.insert(j, hand);
+					inserted = true;
+					break;
+				},
+				Ordering::Equal => if !hand.is_stronger(&cmp) {
+					sorted.insert(j, hand);
+					inserted = true;
+					break;
+				},
+				Ordering::Greater => (),
+			}
+        }
+		if !inserted { sorted.push(hand); }
+    }
+	assert_eq!(hands.len(), sorted.len());
+	let mut sum = 0u64;
+	for (i, h) in sorted.iter().enumerate() {
+		debug!(rank = i + 1, cards = ?h.cards, outcome = ?h.outcome, bid = h.bid);
+		sum += h.bid * (i as u64 + 1);
+	}
+	sum
+}
+
+/// Reads one hand and bid per line.
+pub fn parse(input: &str) -> Result<Vec<Hand>, Error> {
+    parse_lines(input, |x| Hand::new(x.trim(), false))
+}
+
+pub fn part1(hands: &[Hand]) -> u64 { get_winnings(hands) }
+
+/// The winnings once jacks are jokers.
+pub fn part2(hands: &[Hand]) -> u64 {
+    let hands: Vec<Hand> = hands.iter().map(Hand::with_jokers).collect();
+    get_winnings(&hands)
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<Hand>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    const DAY: u8 = 7;
+    #[cfg(feature = "embed-inputs")]
+    const EMBEDDED: Option<&'static str> = Some(include_str!("../input.txt"));
+
+    fn parse(input: &str) -> Result<Self::Input, Error> { parse(input) }
+    fn part1(hands: &Self::Input) -> u64 { part1(hands) }
+    fn part2(hands: &Self::Input) -> u64 { part2(hands) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day7::part1(&Day7::parse(EXAMPLE).unwrap()), 6440);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day7::part2(&Day7::parse(EXAMPLE).unwrap()), 5905);
+    }
+
+    #[test]
+    fn outcomes() {
+        let outcome = |s: &str, jokers| Hand::new(&format!("{s} 1"), jokers).unwrap().outcome;
+        assert_eq!(outcome("AAAAA", false), Outcome::FiveOfAKind);
+        assert_eq!(outcome("23332", false), Outcome::FullHouse);
+        assert_eq!(outcome("23432", false), Outcome::TwoPair);
+        assert_eq!(outcome("23456", false), Outcome::HighCard);
+        assert_eq!(outcome("1111A", true), Outcome::FiveOfAKind);
+        assert_eq!(outcome("22113", true), Outcome::FourOfAKind);
+        assert_eq!(outcome("22133", true), Outcome::FullHouse);
+        assert_eq!(outcome("11111", true), Outcome::FiveOfAKind);
+        assert_eq!(outcome("12345", true), Outcome::Pair);
+    }
+
+    #[test]
+    fn hand_errors() {
+        let e = Hand::new("32T3 765", false).unwrap_err();
+        assert_eq!(e.to_string(), "1:1: expected 5 cards, found `32T3`");
+        let e = Hand::new("32X3K 765", false).unwrap_err();
+        assert_eq!(e.to_string(), "1:3: invalid card `X`");
+        let e = Hand::new("32T3K", false).unwrap_err();
+        assert_eq!(e.to_string(), "1:6: expected a bid");
+        let e = Hand::new("32T3K -5", false).unwrap_err();
+        assert_eq!(e.column, Some(7));
+    }
+}
@@ -0,0 +1,119 @@
+//! Day 9: Mirage Maintenance
+
+use tracing::{debug, trace};
+use utils::{parse_lines, values, Error, Solution};
+
+/// The first and last value of each row of differences of a history.
+#[derive(Debug, Clone)]
+pub struct Reading {
+    starts: Vec<i64>,
+    ends: Vec<i64>,
+}
+
+impl Reading {
+    /// The next value of the history.
+    pub fn extrapolate(&self) -> i64 {
+        self.ends.iter().sum()
+    }
+    /// The value before the first one of the history.
+    pub fn backwards(&self) -> i64 {
+        self.starts.iter().rev().fold(0i64, | sum, x | x - sum)
+    }
+}
+
+/// Parses a line of values, taking differences until they are all zero.
+pub fn extrapolate(s: &str) -> Result<Reading, Error> {
+    let mut v: Vec<i64> = values(s)?;
+    if v.is_empty() { return Err(Error::at(s, s, "expected a list of values")) }
+    let mut r = Reading { ends: vec![], starts: vec![] };
+    loop {
+        let mut done = true;
+        let mut new: Vec<i64> = vec![];
+        r.starts.push(v[0]);
+        for i in 0..v.len() - 1 {
+            let diff = v[i+1] - v[i];
+            if diff != 0 { done = false; }
+            new.push(diff);
+        }
+        r.ends.push(*v.last().unwrap());
+        trace!(row = ?v);
+        if done { break; }
+        v = new;
+    }
+    Ok(r)
+}
+
+/// Reads one history per line.
+pub fn parse(input: &str) -> Result<Vec<Reading>, Error> {
+    parse_lines(input, extrapolate)
+}
+
+/// Sums the next value of every history.
+pub fn part1(readings: &[Reading]) -> i64 {
+    readings.iter()
+            .map(|x| {
+                let next = x.extrapolate();
+                debug!(ends = ?x.ends, next);
+                next
+            })
+            .sum()
+}
+
+/// Sums the value before the start of every history.
+pub fn part2(readings: &[Reading]) -> i64 {
+    readings.iter()
+            .map(|x| {
+                let previous = x.backwards();
+                debug!(starts = ?x.starts, previous);
+                previous
+            })
+            .sum()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<Reading>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    const DAY: u8 = 9;
+    #[cfg(feature = "embed-inputs")]
+    const EMBEDDED: Option<&'static str> = Some(include_str!("../input.txt"));
+
+    fn parse(input: &str) -> Result<Self::Input, Error> { parse(input) }
+    fn part1(readings: &Self::Input) -> i64 { part1(readings) }
+    fn part2(readings: &Self::Input) -> i64 { part2(readings) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day9::part1(&Day9::parse(EXAMPLE).unwrap()), 114);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day9::part2(&Day9::parse(EXAMPLE).unwrap()), 2);
+    }
+
+    #[test]
+    fn readings() {
+        let r = extrapolate("-4 -2 0").unwrap();
+        assert_eq!((r.extrapolate(), r.backwards()), (2, -6));
+        let r = extrapolate("7").unwrap();
+        assert_eq!((r.extrapolate(), r.backwards()), (7, 7));
+        let r = extrapolate("1 2  3").unwrap();
+        assert_eq!((r.extrapolate(), r.backwards()), (4, 0));
+        let e = extrapolate("1 2 x 4").unwrap_err();
+        assert_eq!((e.line, e.column), (Some(1), Some(5)));
+        assert!(extrapolate("0 3 6 9.5 12").is_err());
+        let e = extrapolate("").unwrap_err();
+        assert_eq!(e.to_string(), "1:1: expected a list of values");
+    }
+}
@@ -0,0 +1,228 @@
+//! Finding where a repeatedly applied step starts looping, to jump to a
+//! far-off iteration without simulating every step, and caching the results
+//! of recursive functions whose calls overlap.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where the states produced by repeating a step start to repeat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    /// The first step whose state is part of the loop.
+    pub start: usize,
+    /// The number of steps around the loop.
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start { n } else { self.start + (n - self.start) % self.period }
+    }
+}
+
+/// Finds the cycle of `start`, `step(start)`, … with Floyd's tortoise and
+/// hare, keeping only two states at a time. Loops forever if no state
+/// repeats.
+pub fn floyd<T, F>(start: T, mut step: F) -> Cycle
+    where T: Clone + PartialEq, F: FnMut(&T) -> T {
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+    // the hare is now a multiple of the period ahead, so stepping both from
+    // here and from the start they meet where the loop begins
+    let mut offset = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        offset += 1;
+    }
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+    Cycle { start: offset, period }
+}
+
+/// Like [`floyd`], using Brent's algorithm, which needs fewer steps.
+pub fn brent<T, F>(start: T, mut step: F) -> Cycle
+    where T: Clone + PartialEq, F: FnMut(&T) -> T {
+    let (mut power, mut period) = (1, 1);
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+    let mut offset = 0;
+    tortoise = start.clone();
+    hare = start;
+    for _ in 0..period { hare = step(&hare) }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        offset += 1;
+    }
+    Cycle { start: offset, period }
+}
+
+/// Every state of a repeated step up to its first repeat, so that any later
+/// state can be looked up without stepping again.
+#[derive(Debug, Clone)]
+pub struct History<T> {
+    states: Vec<T>,
+    cycle: Cycle,
+}
+
+impl<T> History<T> {
+    pub fn cycle(&self) -> Cycle { self.cycle }
+    /// The states from the start to the end of the first loop.
+    pub fn states(&self) -> &[T] { &self.states }
+    /// The state after `n` steps.
+    pub fn nth(&self, n: usize) -> &T { &self.states[self.cycle.reduce(n)] }
+}
+
+/// Steps from `start` until a state repeats, recording every state. Loops
+/// forever if no state repeats.
+pub fn history<T, F>(start: T, mut step: F) -> History<T>
+    where T: Clone + Eq + Hash, F: FnMut(&T) -> T {
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = start;
+    loop {
+        match seen.entry(state.clone()) {
+            Entry::Occupied(e) => {
+                let start = *e.get();
+                let cycle = Cycle { start, period: states.len() - start };
+                return History { states, cycle };
+            },
+            Entry::Vacant(e) => { e.insert(states.len()); },
+        }
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/// The state after `n` steps from `start`, skipping the whole loops once a
+/// state repeats.
+pub fn nth_state<T, F>(start: T, mut step: F, n: usize) -> T
+    where T: Clone + Eq + Hash, F: FnMut(&T) -> T {
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = start;
+    while states.len() < n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle { start, period: states.len() - start };
+            return states.swap_remove(cycle.reduce(n));
+        }
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+    state
+}
+
+/// The results of a function by argument.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self { Self { cache: HashMap::new() } }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self { Self::default() }
+
+    /// The result for `key`, computed by `f` the first time. `f` is given
+    /// the memo back, to look up the other keys it depends on.
+    pub fn get(&mut self, key: K, f: impl FnOnce(&mut Self, &K) -> V) -> V {
+        if let Some(v) = self.cache.get(&key) { return v.clone() }
+        let v = f(self, &key);
+        self.cache.insert(key, v.clone());
+        v
+    }
+    pub fn len(&self) -> usize { self.cache.len() }
+    pub fn is_empty(&self) -> bool { self.cache.is_empty() }
+    pub fn clear(&mut self) { self.cache.clear() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2, 5, 26, 677, … modulo 1000, which starts looping after a few steps.
+    fn step(x: &u64) -> u64 { (x * x + 1) % 1000 }
+
+    fn brute_force(n: usize) -> u64 { (0..n).fold(2, |x, _| step(&x)) }
+
+    #[test]
+    fn detection() {
+        let cycle = floyd(2, step);
+        assert_eq!(brent(2, step), cycle);
+        assert_eq!(history(2, step).cycle(), cycle);
+        let Cycle { start, period } = cycle;
+        assert!(period > 1);
+        assert_eq!(brute_force(start), brute_force(start + period));
+        assert_ne!(brute_force(start - 1), brute_force(start + period - 1));
+        assert!((1..period).all(|p| brute_force(start) != brute_force(start + p)));
+
+        // a stream that is a loop from the very start
+        assert_eq!(floyd(0, |x| (x + 1) % 7), Cycle { start: 0, period: 7 });
+        assert_eq!(brent(0, |x| (x + 1) % 7), Cycle { start: 0, period: 7 });
+        assert_eq!(floyd(4, |x| *x), Cycle { start: 0, period: 1 });
+    }
+
+    #[test]
+    fn extrapolation() {
+        let h = history(2, step);
+        let cycle = h.cycle();
+        assert_eq!(h.states().len(), cycle.start + cycle.period);
+        for n in 0..200 {
+            assert_eq!(*h.nth(n), brute_force(n));
+            assert_eq!(nth_state(2, step, n), brute_force(n));
+        }
+        let far = 1_000_000_000;
+        assert_eq!(nth_state(2, step, far), *h.nth(far));
+        assert_eq!(cycle.reduce(cycle.start + 3 * cycle.period), cycle.start);
+        assert_eq!(nth_state(2, step, 0), 2);
+    }
+
+    #[test]
+    fn memo() {
+        fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+            memo.get(n, |memo, &n| if n < 2 { n } else { fib(memo, n - 1) + fib(memo, n - 2) })
+        }
+        let mut memo = Memo::new();
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.len(), 91);
+
+        // the scratchcard cascade: each card wins a copy of the next few
+        let wins = [4, 2, 2, 1, 0, 0];
+        fn total(memo: &mut Memo<usize, u64>, wins: &[usize], i: usize) -> u64 {
+            memo.get(i, |memo, &i| {
+                1 + (i + 1..=i + wins[i]).map(|j| total(memo, wins, j)).sum::<u64>()
+            })
+        }
+        let mut memo = Memo::new();
+        assert_eq!((0..wins.len()).map(|i| total(&mut memo, &wins, i)).sum::<u64>(), 30);
+        memo.clear();
+        assert!(memo.is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// An error loading or parsing puzzle input, pointing at where it happened.
+///
+/// Lines and columns are 1-based. Parsers usually only see part of the input,
+/// so a location is relative to the text that was being parsed and is
+/// translated outwards with [`Error::within`] as the error propagates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+/// Returns the 1-based line and column at which `part` starts, if it is a
+/// subslice of `text`.
+pub fn location(text: &str, part: &str) -> Option<(usize, usize)> {
+    let start = text.as_ptr() as usize;
+    let offset = (part.as_ptr() as usize).checked_sub(start)?;
+    if offset > text.len() || !text.is_char_boundary(offset) { return None }
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |x| x + 1);
+    Some((line, before[line_start..].chars().count() + 1))
+}
+
+impl Error {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self { file: None, line: None, column: None, message: message.into() }
+    }
+
+    /// Creates an error at `part`, a subslice of `text`.
+    pub fn at(text: &str, part: &str, message: impl Into<String>) -> Self {
+        Self::new(message).within(text, part)
+    }
+
+    /// Makes the location of an error raised while parsing `part`, a subslice
+    /// of `text`, relative to `text` instead.
+    pub fn within(mut self, text: &str, part: &str) -> Self {
+        let Some((line, column)) = location(text, part) else { return self };
+        match self.line {
+            None => {
+                self.line = Some(line);
+                self.column = Some(self.column.map_or(column, |c| column + c - 1));
+            },
+            Some(1) => {
+                self.line = Some(line);
+                self.column = self.column.map(|c| column + c - 1);
+            },
+            Some(l) => self.line = Some(line + l - 1),
+        }
+        self
+    }
+
+    pub fn in_file(mut self, file: impl Display) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.file { write!(f, "{file}:")? }
+        if let Some(line) = self.line { write!(f, "{line}:")? }
+        if let Some(column) = self.column { write!(f, "{column}:")? }
+        if self.file.is_some() || self.line.is_some() { write!(f, " ")? }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self { Self::new(e.to_string()) }
+}
+
+/// Parses `token`, a subslice of `text`, reporting its position on failure.
+pub fn parse_token<T>(text: &str, token: &str) -> Result<T, Error>
+    where T: FromStr, T::Err: Display {
+    token.parse::<T>()
+         .map_err(|e| Error::at(text, token, format!("invalid value `{token}`: {e}")))
+}
+
+/// Parses every line of `input` with `f`, locating errors within the input.
+pub fn parse_lines<T, F>(input: &str, mut f: F) -> Result<Vec<T>, Error>
+    where F: FnMut(&str) -> Result<T, Error> {
+    input.lines()
+         .map(|line| f(line).map_err(|e| e.within(input, line)))
+         .collect()
+}
+
+/// Returns the next item of `it`, or an error at the end of `text` naming
+/// what was expected.
+pub fn expect_next<'a>(it: &mut impl Iterator<Item = &'a str>, text: &'a str,
+                       what: &str) -> Result<&'a str, Error> {
+    it.next().ok_or_else(|| Error::at(text, &text[text.len()..],
+                                      format!("expected {what}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locations() {
+        let text = "ab\ncde\nf";
+        assert_eq!(location(text, &text[0..]), Some((1, 1)));
+        assert_eq!(location(text, &text[4..]), Some((2, 2)));
+        assert_eq!(location(text, &text[8..]), Some((3, 2)));
+        assert_eq!(location(text, "elsewhere"), None);
+    }
+
+    #[test]
+    fn nested() {
+        let input = "Seeds: 1 2\n\nmap:\n1 x 3";
+        let section = &input[12..];
+        let line = section.lines().nth(1).unwrap();
+        let e = parse_token::<u64>(line, &line[2..3]).unwrap_err()
+                    .within(section, line)
+                    .within(input, section)
+                    .in_file("input.txt");
+        assert_eq!((e.line, e.column), (Some(4), Some(3)));
+        assert!(e.to_string().starts_with("input.txt:4:3: invalid value `x`"));
+    }
+
+    #[test]
+    fn lines() {
+        let res = parse_lines("1\n2\nthree", |l| parse_token::<u8>(l, l));
+        assert_eq!(res.unwrap_err().line, Some(3));
+        let mut it = "a".split(' ');
+        it.next();
+        let e = expect_next(&mut it, "a", "a number").unwrap_err();
+        assert_eq!(e.to_string(), "1:2: expected a number");
+    }
+}
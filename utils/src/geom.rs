@@ -0,0 +1,181 @@
+//! Points and directions on a plane whose `y` axis points down, as when
+//! reading a character map.
+
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position or offset with signed coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self { Self { x, y } }
+
+    /// The number of orthogonal steps between `self` and `other`.
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+    /// The number of steps between `self` and `other` when diagonal steps
+    /// are allowed.
+    pub fn chebyshev(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The point one step away in `direction`.
+    pub fn step(self, direction: Direction) -> Self { self + direction.offset() }
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Direction::ORTHOGONAL.into_iter().map(move |d| self.step(d))
+    }
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// The `(x, y)` cell of a `width` × `height` grid at this point, if it is
+    /// inside the grid.
+    pub fn to_cell(self, width: usize, height: usize) -> Option<(usize, usize)> {
+        let x = usize::try_from(self.x).ok().filter(|&x| x < width)?;
+        let y = usize::try_from(self.y).ok().filter(|&y| y < height)?;
+        Some((x, y))
+    }
+    /// The row-major index of this point into a `width` × `height` grid, if
+    /// it is inside the grid.
+    pub fn to_index(self, width: usize, height: usize) -> Option<usize> {
+        self.to_cell(width, height).map(|(x, y)| y * width + x)
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self { Self::new(x as i64, y as i64) }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+    fn add(self, other: Self) -> Self { Self::new(self.x + other.x, self.y + other.y) }
+}
+
+impl Sub for Point {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self { Self::new(self.x - other.x, self.y - other.y) }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Self) { *self = *self + other }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Self) { *self = *self - other }
+}
+
+impl Neg for Point {
+    type Output = Self;
+    fn neg(self) -> Self { Self::new(-self.x, -self.y) }
+}
+
+/// Scales both coordinates.
+impl Mul<i64> for Point {
+    type Output = Self;
+    fn mul(self, factor: i64) -> Self { Self::new(self.x * factor, self.y * factor) }
+}
+
+/// A compass direction, north being up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Self; 8] = [
+        Self::N, Self::NE, Self::E, Self::SE, Self::S, Self::SW, Self::W, Self::NW,
+    ];
+    /// The four orthogonal directions, clockwise from north.
+    pub const ORTHOGONAL: [Self; 4] = [Self::N, Self::E, Self::S, Self::W];
+
+    /// The offset of one step in this direction.
+    pub const fn offset(self) -> Point {
+        match self {
+            Self::N  => Point::new(0, -1),
+            Self::NE => Point::new(1, -1),
+            Self::E  => Point::new(1, 0),
+            Self::SE => Point::new(1, 1),
+            Self::S  => Point::new(0, 1),
+            Self::SW => Point::new(-1, 1),
+            Self::W  => Point::new(-1, 0),
+            Self::NW => Point::new(-1, -1),
+        }
+    }
+    pub fn is_diagonal(self) -> bool { !Self::ORTHOGONAL.contains(&self) }
+
+    /// Turns `eighths` eighths of a full turn clockwise.
+    fn turn(self, eighths: usize) -> Self { Self::ALL[(self as usize + eighths) % 8] }
+    /// Turns a quarter clockwise.
+    pub fn right(self) -> Self { self.turn(2) }
+    /// Turns a quarter counterclockwise.
+    pub fn left(self) -> Self { self.turn(6) }
+    pub fn opposite(self) -> Self { self.turn(4) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(1, -2);
+        let b = Point::new(-3, 4);
+        assert_eq!(a + b, Point::new(-2, 2));
+        assert_eq!(a - b, Point::new(4, -6));
+        assert_eq!(-a * 3, Point::new(-3, 6));
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(a.to_string(), "(1, -2)");
+    }
+
+    #[test]
+    fn directions() {
+        let p = Point::ORIGIN;
+        assert_eq!(p.step(Direction::N), Point::new(0, -1));
+        assert_eq!(p.step(Direction::SW), Point::new(-1, 1));
+        assert_eq!(Direction::N.right(), Direction::E);
+        assert_eq!(Direction::W.right(), Direction::N);
+        assert_eq!(Direction::N.left(), Direction::W);
+        assert_eq!(Direction::NE.opposite(), Direction::SW);
+        assert!(Direction::SE.is_diagonal() && !Direction::S.is_diagonal());
+        assert!(p.neighbours8().all(|n| p.chebyshev(n) == 1));
+        assert!(p.neighbours4().all(|n| p.manhattan(n) == 1));
+        let sum = Direction::ALL.iter().fold(p, |sum, d| sum + d.offset());
+        assert_eq!(sum, p);
+    }
+
+    #[test]
+    fn cells() {
+        assert_eq!(Point::new(2, 1).to_cell(3, 2), Some((2, 1)));
+        assert_eq!(Point::new(2, 1).to_index(3, 2), Some(5));
+        assert_eq!(Point::new(3, 1).to_index(3, 2), None);
+        assert_eq!(Point::new(0, 2).to_index(3, 2), None);
+        assert_eq!(Point::new(-1, 0).to_cell(3, 2), None);
+        assert_eq!(Point::from((4, 7)), Point::new(4, 7));
+    }
+}
@@ -0,0 +1,246 @@
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+use crate::geom::{Direction, Point};
+use crate::Error;
+
+/// A rectangular grid of cells stored row-major.
+///
+/// Cells are addressed by `(x, y)`, the column and row counted from the top
+/// left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    data: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a `width` × `height` grid filled with `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self where T: Clone {
+        Self { width, height, data: vec![value; width * height] }
+    }
+
+    /// Parses a character map, one row per line, converting each character
+    /// with `f`. Rows must all be as wide as the first one.
+    pub fn parse<F>(input: &str, mut f: F) -> Result<Self, Error>
+        where F: FnMut(char) -> Option<T> {
+        let width = input.lines().next().map_or(0, |x| x.chars().count());
+        let mut data = Vec::new();
+        let mut height = 0;
+        for line in input.lines() {
+            let len = line.chars().count();
+            if len != width {
+                let end = line.char_indices().nth(width).map_or(line.len(), |x| x.0);
+                return Err(Error::at(input, &line[end..],
+                    format!("expected {width} columns, found {len}")));
+            }
+            for (i, c) in line.char_indices() {
+                let cell = f(c).ok_or_else(|| {
+                    Error::at(input, &line[i..], format!("invalid character `{c}`"))
+                })?;
+                data.push(cell);
+            }
+            height += 1;
+        }
+        Ok(Self { width, height, data })
+    }
+
+    pub fn width(&self) -> usize { self.width }
+    pub fn height(&self) -> usize { self.height }
+    pub fn is_empty(&self) -> bool { self.data.is_empty() }
+
+    /// The offset into the row-major cells of `(x, y)`, if it is in bounds.
+    pub fn offset(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+    /// The `(x, y)` position of the cell at row-major `offset`, if there is
+    /// such a cell.
+    pub fn position(&self, offset: usize) -> Option<(usize, usize)> {
+        (offset < self.data.len()).then(|| (offset % self.width, offset / self.width))
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.offset(x, y).map(|i| &self.data[i])
+    }
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.offset(x, y).map(|i| &mut self.data[i])
+    }
+    /// The cell at `p`, if it is inside the grid.
+    pub fn at(&self, p: Point) -> Option<&T> {
+        p.to_index(self.width, self.height).map(|i| &self.data[i])
+    }
+    pub fn contains(&self, p: Point) -> bool {
+        p.to_index(self.width, self.height).is_some()
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y))).zip(&self.data)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.data[y * self.width..(y + 1) * self.width]
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.data.iter().skip(x).step_by(self.width)
+    }
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    fn steps<'a>(&self, x: usize, y: usize, directions: &'a [Direction]) ->
+                 impl Iterator<Item = (usize, usize)> + 'a {
+        let (width, height) = (self.width, self.height);
+        let p = Point::from((x, y));
+        directions.iter().filter_map(move |&d| p.step(d).to_cell(width, height))
+    }
+    /// The in-bounds positions above, right of, below and left of `(x, y)`.
+    pub fn neighbours4(&self, x: usize, y: usize) ->
+                       impl Iterator<Item = (usize, usize)> {
+        self.steps(x, y, &Direction::ORTHOGONAL)
+    }
+    /// The in-bounds positions around `(x, y)`, diagonals included.
+    pub fn neighbours8(&self, x: usize, y: usize) ->
+                       impl Iterator<Item = (usize, usize)> {
+        self.steps(x, y, &Direction::ALL)
+    }
+
+    /// Converts every cell with `f`, keeping its position.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        let data = self.data.iter().map(f).collect();
+        Grid { width: self.width, height: self.height, data }
+    }
+
+    /// Builds a `width` × `height` grid whose cell at `(x, y)` is `f(x, y)`.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let data = (0..width * height).map(|i| f(i % width, i / width)).collect();
+        Self { width, height, data }
+    }
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self where T: Clone {
+        Self::from_fn(self.height, self.width, |x, y| self[(y, x)].clone())
+    }
+    /// Turns the grid a quarter clockwise, the first row becoming the last
+    /// column.
+    pub fn rotate_cw(&self) -> Self where T: Clone {
+        Self::from_fn(self.height, self.width, |x, y| self[(y, self.height - 1 - x)].clone())
+    }
+    /// Turns the grid a quarter counterclockwise, the first row becoming the
+    /// first column read upwards.
+    pub fn rotate_ccw(&self) -> Self where T: Clone {
+        Self::from_fn(self.height, self.width, |x, y| self[(self.width - 1 - y, x)].clone())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).unwrap_or_else(|| {
+            panic!("({x}, {y}) out of bounds of {}x{} grid", self.width, self.height)
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(x, y).unwrap_or_else(|| {
+            panic!("({x}, {y}) out of bounds of {width}x{height} grid")
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+    fn index(&self, p: Point) -> &T {
+        self.at(p).unwrap_or_else(|| {
+            panic!("{p} out of bounds of {}x{} grid", self.width, self.height)
+        })
+    }
+}
+
+/// Writes one line per row with the cells side by side.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row { write!(f, "{cell}")? }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(input: &str) -> Grid<char> { Grid::parse(input, Some).unwrap() }
+
+    #[test]
+    fn parse() {
+        let g = chars("abc\ndef");
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g.get(2, 1), Some(&'f'));
+        assert_eq!(g.get(1, 2), None);
+        assert_eq!(g.get(3, 0), None);
+        assert_eq!(g[(0, 1)], 'd');
+        assert_eq!(g[Point::new(0, 1)], 'd');
+        assert_eq!(g.at(Point::new(-1, 1)), None);
+        assert!(g.contains(Point::new(2, 1)) && !g.contains(Point::new(2, 2)));
+        assert_eq!((g.offset(2, 1), g.offset(3, 0)), (Some(5), None));
+        assert_eq!(g.position(5), Some((2, 1)));
+        assert_eq!(g.position(6), None);
+        assert_eq!(g.to_string(), "abc\ndef\n");
+        let empty = chars("");
+        assert!(empty.is_empty());
+        assert_eq!((empty.width(), empty.position(0), empty.iter().count()), (0, None, 0));
+
+        let e = Grid::parse("ab\nc", Some).unwrap_err();
+        assert_eq!(e.to_string(), "2:2: expected 2 columns, found 1");
+        let e = Grid::parse("ab\nabc", Some).unwrap_err();
+        assert_eq!(e.to_string(), "2:3: expected 2 columns, found 3");
+        let e = Grid::parse("#.\n.x", |c| (c != 'x').then_some(c)).unwrap_err();
+        assert_eq!(e.to_string(), "2:2: invalid character `x`");
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let g = chars("abc\ndef");
+        let rows: Vec<String> = g.rows().map(|r| r.iter().collect()).collect();
+        assert_eq!(rows, vec!["abc", "def"]);
+        let columns: Vec<String> = g.columns().map(|c| c.collect()).collect();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+        let cells: Vec<_> = g.iter().filter(|x| *x.1 == 'e').collect();
+        assert_eq!(cells, vec![((1, 1), &'e')]);
+    }
+
+    #[test]
+    fn neighbours() {
+        let g = Grid::new(3, 2, 0u8);
+        assert_eq!(g.neighbours4(0, 0).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+        assert_eq!(g.neighbours4(1, 1).collect::<Vec<_>>(), vec![(1, 0), (2, 1), (0, 1)]);
+        assert_eq!(g.neighbours8(2, 0).collect::<Vec<_>>(), vec![(2, 1), (1, 1), (1, 0)]);
+        assert_eq!(g.neighbours8(1, 0).count(), 5);
+    }
+
+    #[test]
+    fn transforms() {
+        let g = chars("abc\ndef");
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(g.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(g.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(g.rotate_cw().rotate_ccw(), g);
+        assert_eq!(g.map(|c| c.is_ascii_uppercase()), Grid::new(3, 2, false));
+
+        let mut g = g;
+        g[(2, 1)] = 'x';
+        *g.get_mut(0, 0).unwrap() = 'y';
+        assert_eq!(g.to_string(), "ybc\ndex\n");
+    }
+}
@@ -1,32 +1,228 @@
+use std::borrow::Cow;
+use std::fmt::{self, Display};
 use std::fs::{File, read_to_string};
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, IsTerminal, Read};
 use std::path::{Path, PathBuf};
+use tracing::info_span;
+use tracing_subscriber::EnvFilter;
 
-fn workspace_dir() -> PathBuf {
-    let output = std::process::Command::new(env!("CARGO"))
-        .arg("locate-project")
-        .arg("--workspace")
-        .arg("--message-format=plain")
-        .output()
-        .unwrap()
-        .stdout;
-    let cargo_path = Path::new(std::str::from_utf8(&output).unwrap().trim());
-    cargo_path.parent().unwrap().to_path_buf()
+pub mod cycle;
+mod error;
+pub mod geom;
+mod grid;
+pub mod math;
+pub mod ranges;
+#[cfg(feature = "render")]
+pub mod render;
+pub mod search;
+mod text;
+
+pub use error::{Error, expect_next, location, parse_lines, parse_token};
+pub use grid::Grid;
+pub use text::{record, sections, split_header, split_label, values};
+
+/// A single day's puzzle, split into a parsing step shared by both parts and
+/// the two part solvers.
+pub trait Solution {
+    /// The parsed representation of the puzzle input.
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    /// The puzzle day, used to locate `src/day<N>/input.txt`.
+    const DAY: u8;
+    /// The day's `input.txt` when compiled in with the crate's
+    /// `embed-inputs` feature, used in place of reading the default file.
+    const EMBEDDED: Option<&'static str> = None;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    /// Draws the input for a terminal, highlighting what the answers are
+    /// made of, for the days that support it.
+    #[cfg(feature = "render")]
+    fn visualize(_input: &Self::Input) -> Option<String> { None }
+    /// Like [`Solution::visualize`], as a picture to save as an image file.
+    #[cfg(feature = "render")]
+    fn picture(_input: &Self::Input) -> Option<render::Picture> { None }
+}
+
+/// Returns the workspace-relative path of the input file for `day`.
+pub fn input_path(day: u8) -> String {
+    format!("src/day{day}/input.txt")
 }
 
-pub fn read_input(filepath: &str) -> Result<String, std::io::Error> {
-	let mut path: PathBuf = workspace_dir();
-	path.push(Path::new(filepath));
-    read_to_string(path.as_path())
+/// Environment variable overriding the input path when no argument is given.
+/// Any `{day}` in its value is replaced by the day number.
+pub const INPUT_VAR: &str = "AOC_INPUT";
+
+/// The value of `AOC_INPUT`, if it is set.
+pub fn input_var() -> Option<String> {
+    std::env::var(INPUT_VAR).ok()
+}
+
+/// Where a day's puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The day's `src/day<N>/input.txt` in the workspace.
+    Default(u8),
+    /// A file path, relative to the current directory.
+    Path(PathBuf),
+    /// Standard input, selected with `-`.
+    Stdin,
 }
 
-pub fn read_lines(filepath: &str) -> Vec<String> {
-	let mut path: PathBuf = workspace_dir();
-	path.push(Path::new(filepath));
-    let file = File::open(path.as_path()).expect("no such file");
+impl InputSource {
+    /// Picks the input for `day` from an explicit argument, then the
+    /// `AOC_INPUT` environment variable, then the default location.
+    pub fn resolve(day: u8, arg: Option<&str>) -> Self {
+        Self::choose(day, arg, input_var().as_deref())
+    }
+    /// Like [`InputSource::resolve`], with `var` as the value of `AOC_INPUT`,
+    /// ignored when empty.
+    pub fn choose(day: u8, arg: Option<&str>, var: Option<&str>) -> Self {
+        match arg.or(var.filter(|x| !x.is_empty())) {
+            Some(x) => Self::from_arg(day, x),
+            None => Self::Default(day),
+        }
+    }
+    fn from_arg(day: u8, arg: &str) -> Self {
+        match arg {
+            "-" => Self::Stdin,
+            _ => Self::Path(PathBuf::from(arg.replace("{day}", &day.to_string()))),
+        }
+    }
+    pub fn read(&self) -> Result<String, Error> {
+        match self {
+            Self::Default(day) => read_input(&input_path(*day)),
+            Self::Path(path) => {
+                read_to_string(path).map_err(|e| Error::from(e).in_file(self))
+            },
+            Self::Stdin => {
+                let mut s = String::new();
+                std::io::stdin().read_to_string(&mut s)
+                                .map_err(|e| Error::from(e).in_file(self))?;
+                Ok(s)
+            },
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Default(day) => {
+                write!(f, "{}", workspace_dir().join(input_path(*day)).display())
+            },
+            Self::Path(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// A puzzle input as read, with the name its errors are reported under.
+#[derive(Debug, Clone)]
+pub struct RawInput {
+    pub text: Cow<'static, str>,
+    pub name: String,
+}
+
+/// Reads the input for `S`, serving the default input from memory when it
+/// has been embedded.
+pub fn read<S: Solution>(source: &InputSource) -> Result<RawInput, Error> {
+    match (source, S::EMBEDDED) {
+        (InputSource::Default(day), Some(data)) => Ok(RawInput {
+            text: Cow::Borrowed(data),
+            name: format!("<embedded day {day} input>"),
+        }),
+        _ => Ok(RawInput { text: Cow::Owned(source.read()?), name: source.to_string() }),
+    }
+}
+
+/// Reads and parses the input for `S`, attaching the input's name to errors.
+pub fn load<S: Solution>(source: &InputSource) -> Result<S::Input, Error> {
+    let input = read::<S>(source)?;
+    S::parse(&input.text).map_err(|e| e.in_file(&input.name))
+}
+
+/// Prints the solvers' `tracing` events to stderr. With `verbose` at 0 the
+/// `RUST_LOG` environment variable picks what is shown, warnings only if it
+/// is unset; 1 shows the intermediate results and 2 or more every step.
+pub fn init_tracing(verbose: u8) {
+    let filter = match verbose {
+        0 => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")),
+        1 => EnvFilter::new("debug"),
+        _ => EnvFilter::new("trace"),
+    };
+    _ = tracing_subscriber::fmt().with_env_filter(filter)
+                                 .with_writer(std::io::stderr)
+                                 .with_ansi(std::io::stderr().is_terminal())
+                                 .without_time()
+                                 .try_init();
+}
+
+/// Reads the input for `S`, solves both parts and prints the answers.
+///
+/// The input is taken from the first command line argument if present, see
+/// [`InputSource::resolve`]. Exits the process if it cannot be loaded.
+/// `RUST_LOG` shows the solvers' intermediate steps, see [`init_tracing`].
+pub fn run<S: Solution>() -> (S::Answer1, S::Answer2) {
+    init_tracing(0);
+    let _day = info_span!("day", day = S::DAY).entered();
+    let arg = std::env::args().nth(1);
+    let source = InputSource::resolve(S::DAY, arg.as_deref());
+    let input = info_span!("parse").in_scope(|| load::<S>(&source)).unwrap_or_else(|e| {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    });
+
+    let part1 = info_span!("part", part = 1).in_scope(|| S::part1(&input));
+    println!("day{}, part 1", S::DAY);
+    println!("  {part1}");
+
+    let part2 = info_span!("part", part = 2).in_scope(|| S::part2(&input));
+    println!("day{}, part 2", S::DAY);
+    println!("  {part2}");
+    (part1, part2)
+}
+
+/// Environment variable overriding the directory that `src/day<N>/input.txt`
+/// and the other workspace files are resolved against.
+pub const ROOT_VAR: &str = "AOC_ROOT";
+
+/// Returns the root directory of the cargo workspace, as it was at compile
+/// time unless overridden by `AOC_ROOT`.
+pub fn workspace_dir() -> PathBuf {
+    match std::env::var_os(ROOT_VAR).filter(|x| !x.is_empty()) {
+        Some(root) => PathBuf::from(root),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf(),
+    }
+}
+
+fn open_error(e: std::io::Error, path: &Path, filepath: &str) -> Error {
+    let not_found = e.kind() == std::io::ErrorKind::NotFound;
+    let mut err = Error::from(e).in_file(path.display());
+    if not_found {
+        err.message = format!("{}, set {ROOT_VAR} to the directory containing `{filepath}`",
+                              err.message);
+    }
+    err
+}
+
+pub fn read_input(filepath: &str) -> Result<String, Error> {
+    let path: PathBuf = workspace_dir().join(filepath);
+    read_to_string(path.as_path()).map_err(|e| open_error(e, &path, filepath))
+}
+
+pub fn read_lines(filepath: &str) -> Result<Vec<String>, Error> {
+    let path: PathBuf = workspace_dir().join(filepath);
+    let file = File::open(path.as_path()).map_err(|e| open_error(e, &path, filepath))?;
     let buf = BufReader::new(file);
     buf.lines()
-        .map(|l| l.expect("Could not parse line"))
+        .enumerate()
+        .map(|(i, l)| l.map_err(|e| Error { line: Some(i + 1), ..Error::from(e) }
+                                   .in_file(path.display())))
         .collect()
 }
 
@@ -39,6 +235,45 @@ mod tests {
         let dir = "utils/input.txt";
         assert_eq!("hello world\nhello earth",
             read_input(dir).unwrap());
-        assert_eq!(vec!["hello world", "hello earth"], read_lines(&dir));
+        assert_eq!(vec!["hello world", "hello earth"], read_lines(dir).unwrap());
+    }
+
+    struct Embedded;
+
+    impl Solution for Embedded {
+        type Input = u32;
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        const DAY: u8 = 4;
+        const EMBEDDED: Option<&'static str> = Some("x");
+
+        fn parse(input: &str) -> Result<u32, Error> { parse_token(input, input) }
+        fn part1(n: &u32) -> u32 { *n }
+        fn part2(n: &u32) -> u32 { *n }
+    }
+
+    #[test]
+    fn embedded_input() {
+        let e = load::<Embedded>(&InputSource::Default(4)).unwrap_err();
+        assert!(e.to_string().starts_with("<embedded day 4 input>:1:1: "), "{e}");
+        let e = load::<Embedded>(&InputSource::Path(PathBuf::from("input.txt"))).unwrap_err();
+        assert!(e.to_string().starts_with("input.txt:1:1: "), "{e}");
+    }
+
+    #[test]
+    fn input_source() {
+        assert_eq!(InputSource::from_arg(3, "-"), InputSource::Stdin);
+        assert_eq!(InputSource::from_arg(3, "inputs/day{day}.txt"),
+            InputSource::Path(PathBuf::from("inputs/day3.txt")));
+        assert_eq!(InputSource::resolve(3, Some("example.txt")),
+            InputSource::Path(PathBuf::from("example.txt")));
+        assert_eq!(InputSource::choose(3, None, Some("day{day}.txt")),
+            InputSource::Path(PathBuf::from("day3.txt")));
+        assert_eq!(InputSource::choose(3, Some("-"), Some("day{day}.txt")), InputSource::Stdin);
+        assert_eq!(InputSource::choose(3, None, Some("")), InputSource::Default(3));
+        // tests run from the package directory
+        assert_eq!("hello world\nhello earth",
+            InputSource::Path(PathBuf::from("input.txt")).read().unwrap());
     }
 }
@@ -0,0 +1,177 @@
+//! Number theory for puzzles about cycles lining up. Everything that could
+//! overflow is checked and returns `None` instead.
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 { (a, b) = (b, a % b) }
+    a
+}
+
+/// The least common multiple of `a` and `b`, or `None` if it overflows.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 { return Some(0) }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// The greatest common divisor of all `values`, 0 if there are none.
+pub fn gcd_all(values: impl IntoIterator<Item = u64>) -> u64 {
+    values.into_iter().fold(0, gcd)
+}
+
+/// The least common multiple of all `values`, 1 if there are none.
+pub fn lcm_all(values: impl IntoIterator<Item = u64>) -> Option<u64> {
+    values.into_iter().try_fold(1, lcm)
+}
+
+/// Returns `(g, x, y)` with `g` the non-negative gcd of `a` and `b` and
+/// `a * x + b * y == g`, or `None` if `g` does not fit, as for the gcd 2^63
+/// of `i64::MIN` and 0.
+pub fn extended_gcd(a: i64, b: i64) -> Option<(i64, i64, i64)> {
+    let (g, x, y) = extended_gcd_wide(a as i128, b as i128);
+    Some((i64::try_from(g).ok()?, i64::try_from(x).ok()?, i64::try_from(y).ok()?))
+}
+
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 { (-r0, -x0, -y0) } else { (r0, x0, y0) }
+}
+
+/// The inverse of `a` modulo `m`, if they are coprime.
+pub fn mod_inverse(a: u64, m: u64) -> Option<u64> {
+    if m == 0 { return None }
+    let (g, x, _) = extended_gcd_wide(a as i128, m as i128);
+    (g == 1).then(|| x.rem_euclid(m as i128) as u64)
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)`, which need
+/// not have coprime moduli. Returns the smallest non-negative solution and
+/// the modulus of all solutions, or `None` if the congruences contradict
+/// each other, a modulus is 0 or the combined modulus overflows.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, u64)>) -> Option<(u64, u64)> {
+    let (mut x, mut m) = (0i128, 1i128);
+    for (residue, modulus) in congruences {
+        if modulus == 0 { return None }
+        let (a, n) = (residue as i128, modulus as i128);
+        let (g, p, _) = extended_gcd_wide(m, n);
+        if (a - x) % g != 0 { return None }
+        let step = n / g;
+        let lcm = m.checked_mul(step).filter(|&lcm| lcm <= u64::MAX as i128)?;
+        // step x by multiples of m, which keep the earlier congruences; both
+        // factors are below `step`, so neither product overflows
+        let k = (a - x) / g % step * p.rem_euclid(step) % step;
+        x = (x + m * k).rem_euclid(lcm);
+        m = lcm;
+    }
+    Some((x as u64, m as u64))
+}
+
+/// `base` to the power `exp` modulo `modulus`, or `None` if `modulus` is 0.
+pub fn modpow(base: u64, mut exp: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 { return None }
+    let m = modulus as u128;
+    let mut base = base as u128 % m;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 { result = result * base % m }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    Some(result as u64)
+}
+
+/// The largest integer whose square is at most `n`.
+pub fn isqrt(n: u64) -> u64 { isqrt_u128(n as u128) as u64 }
+
+/// The largest integer whose square is at most `n`.
+pub fn isqrt_u128(n: u128) -> u128 {
+    if n < 2 { return n }
+    // a float estimate is within a few thousand of the root; one Newton step
+    // brings it within one, which the loops then correct exactly
+    let mut x = (n as f64).sqrt() as u128;
+    x = (x + n / x) / 2;
+    while x.checked_mul(x).is_none_or(|sq| sq > n) { x -= 1 }
+    while (x + 1).checked_mul(x + 1).is_some_and(|sq| sq <= n) { x += 1 }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn divisors() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(gcd_all([12, 18, 27]), 3);
+        assert_eq!(gcd_all([]), 0);
+        assert_eq!(lcm_all([2, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all([]), Some(1));
+        assert_eq!(lcm_all([u64::MAX, u64::MAX - 1]), None);
+        assert_eq!(lcm(1 << 63, 1 << 62), Some(1 << 63));
+    }
+
+    #[test]
+    fn extended_euclid() {
+        for (a, b) in [(240, 46), (-240, 46), (17, 0), (0, -5), (i64::MAX, i64::MIN + 1)] {
+            let (g, x, y) = extended_gcd(a, b).unwrap();
+            assert!(g >= 0);
+            assert_eq!(a as i128 * x as i128 + b as i128 * y as i128, g as i128);
+        }
+        assert_eq!(extended_gcd(240, 46).map(|x| x.0), Some(2));
+        assert_eq!(extended_gcd(i64::MIN, 0), None);
+        assert_eq!(extended_gcd(i64::MIN, i64::MIN), None);
+        assert_eq!(extended_gcd(i64::MIN, 1), Some((1, 0, 1)));
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_inverse(3, 0), None);
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // non-coprime moduli
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(0, 0)]), None);
+        // moduli whose product overflows but whose lcm does not
+        let big = 1u64 << 62;
+        assert_eq!(crt([(5, big), (5, big * 2)]), Some((5, big * 2)));
+        assert_eq!(crt([(0, u64::MAX), (1, u64::MAX - 1)]), None);
+    }
+
+    #[test]
+    fn powers() {
+        assert_eq!(modpow(4, 13, 497), Some(445));
+        assert_eq!(modpow(2, 0, 1), Some(0));
+        assert_eq!(modpow(u64::MAX, u64::MAX, u64::MAX - 1), Some(1));
+        assert_eq!(modpow(u64::MAX - 1, 2, u64::MAX), Some(1));
+        assert_eq!(modpow(3, 200, u64::MAX), Some(modpow(9, 100, u64::MAX).unwrap()));
+        assert_eq!(modpow(2, 10, 0), None);
+    }
+
+    #[test]
+    fn square_roots() {
+        for n in 0..1000u64 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n);
+        }
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        let r = u32::MAX as u64 - 7;
+        assert_eq!((isqrt(r * r), isqrt(r * r - 1)), (r, r - 1));
+        assert_eq!(isqrt_u128(u128::MAX), u64::MAX as u128);
+        let r = (1u128 << 60) + 12345;
+        assert_eq!(isqrt_u128(r * r), r);
+        assert_eq!(isqrt_u128(r * r - 1), r - 1);
+    }
+}
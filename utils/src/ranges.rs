@@ -0,0 +1,218 @@
+//! Sets of half-open ranges and piecewise offsets between ranges, for
+//! puzzles whose inputs are too large to handle one number at a time.
+
+use std::ops::{Add, Range, Sub};
+
+/// A set of values stored as sorted, disjoint, non-adjacent ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self { Self { ranges: Vec::new() } }
+}
+
+impl<T: Copy + Ord> RangeSet<T> {
+    pub fn new() -> Self { Self::default() }
+
+    /// Sorts `ranges`, dropping empty ones and merging those that overlap or
+    /// touch.
+    fn normalize(mut ranges: Vec<Range<T>>) -> Self {
+        ranges.retain(|r| r.start < r.end);
+        ranges.sort_unstable_by_key(|r| r.start);
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for r in ranges {
+            match merged.last_mut() {
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => merged.push(r),
+            }
+        }
+        Self { ranges: merged }
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] { &self.ranges }
+    pub fn is_empty(&self) -> bool { self.ranges.is_empty() }
+    /// The smallest value in the set.
+    pub fn min(&self) -> Option<T> { self.ranges.first().map(|r| r.start) }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.push(range);
+        *self = Self::normalize(ranges);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalize(self.ranges.iter().chain(&other.ranges).cloned().collect())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = Vec::new();
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let (start, end) = (a.start.max(b.start), a.end.min(b.end));
+            if start < end { ranges.push(start..end) }
+            if a.end < b.end { i += 1 } else { j += 1 }
+        }
+        Self { ranges }
+    }
+
+    /// The values of `self` that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+        for r in &self.ranges {
+            let mut start = r.start;
+            while let Some(o) = other.ranges.get(j) {
+                if o.end <= start { j += 1; continue }
+                if o.start >= r.end { break }
+                if o.start > start { ranges.push(start..o.start) }
+                start = o.end;
+                if start >= r.end { break }
+                j += 1;
+            }
+            if start < r.end { ranges.push(start..r.end) }
+        }
+        Self { ranges }
+    }
+
+    /// Splits the set into the values below `at` and the rest.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = Vec::new();
+        let mut above = Vec::new();
+        for r in &self.ranges {
+            if r.end <= at { below.push(r.clone()) }
+            else if r.start >= at { above.push(r.clone()) }
+            else {
+                below.push(r.start..at);
+                above.push(at..r.end);
+            }
+        }
+        (Self { ranges: below }, Self { ranges: above })
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::normalize(iter.into_iter().collect())
+    }
+}
+
+/// A piecewise mapping moving each of a set of disjoint source ranges to its
+/// own destination. Values outside every source range map to themselves.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeMap<T> {
+    /// Source ranges sorted by start, with the start of their destination.
+    entries: Vec<(Range<T>, T)>,
+}
+
+impl<T> Default for RangeMap<T> {
+    fn default() -> Self { Self { entries: Vec::new() } }
+}
+
+impl<T> RangeMap<T> where T: Copy + Ord + Add<Output = T> + Sub<Output = T> {
+    pub fn new() -> Self { Self::default() }
+
+    /// Maps `source` to the range of the same length starting at `dest`.
+    /// Fails with the existing source range if the two overlap.
+    pub fn insert(&mut self, source: Range<T>, dest: T) -> Result<(), Range<T>> {
+        if source.start >= source.end { return Ok(()) }
+        let i = self.entries.partition_point(|e| e.0.start < source.start);
+        let overlaps = |e: &(Range<T>, T)| e.0.start < source.end && source.start < e.0.end;
+        // only the neighbours on either side can overlap
+        let around = [i.checked_sub(1), Some(i)].into_iter().flatten();
+        if let Some(e) = around.filter_map(|i| self.entries.get(i)).find(|e| overlaps(e)) {
+            return Err(e.0.clone());
+        }
+        self.entries.insert(i, (source, dest));
+        Ok(())
+    }
+
+    /// The source range containing `value` and the start of its
+    /// destination, if any.
+    pub fn entry(&self, value: T) -> Option<(Range<T>, T)> {
+        let i = self.entries.partition_point(|e| e.0.end <= value);
+        self.entries.get(i).filter(|e| e.0.start <= value).cloned()
+    }
+
+    pub fn get(&self, value: T) -> T {
+        match self.entry(value) {
+            Some((r, dest)) => dest + (value - r.start),
+            None => value,
+        }
+    }
+
+    /// Maps every value of `range`, which may be split across several
+    /// source ranges and the gaps between them.
+    pub fn map_range(&self, range: Range<T>) -> RangeSet<T> {
+        let mut out = Vec::new();
+        let mut cursor = range.start;
+        let first = self.entries.partition_point(|e| e.0.end <= range.start);
+        for (r, dest) in &self.entries[first..] {
+            if r.start >= range.end { break }
+            if r.start > cursor { out.push(cursor..r.start) }
+            let (start, end) = (cursor.max(r.start), range.end.min(r.end));
+            out.push(*dest + (start - r.start)..*dest + (end - r.start));
+            cursor = end;
+        }
+        if cursor < range.end { out.push(cursor..range.end) }
+        out.into_iter().collect()
+    }
+
+    pub fn map_set(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        set.ranges().iter().flat_map(|r| self.map_range(r.clone()).ranges).collect()
+    }
+}
+
+#[cfg(test)]
+// single ranges are the expected results, not a misspelt `Vec` of numbers
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<i64>]) -> RangeSet<i64> { ranges.iter().cloned().collect() }
+
+    #[test]
+    fn range_set() {
+        let a = set(&[5..8, 0..2, 1..3, 3..4, 9..9]);
+        assert_eq!(a.ranges(), &[0..4, 5..8]);
+        assert!(a.contains(0) && a.contains(7) && !a.contains(4) && !a.contains(8));
+        assert_eq!(a.min(), Some(0));
+        let b = set(&[2..6, 7..10]);
+        assert_eq!(a.union(&b).ranges(), &[0..10]);
+        assert_eq!(a.intersection(&b).ranges(), &[2..4, 5..6, 7..8]);
+        assert_eq!(a.difference(&b).ranges(), &[0..2, 6..7]);
+        assert_eq!(b.difference(&a).ranges(), &[4..5, 8..10]);
+        assert_eq!(a.difference(&set(&[-5..20])), RangeSet::new());
+        let (below, above) = a.split_at(6);
+        assert_eq!((below.ranges(), above.ranges()), (&[0..4, 5..6][..], &[6..8][..]));
+        let mut c = RangeSet::new();
+        c.insert(3..5);
+        c.insert(-1..3);
+        assert_eq!(c.ranges(), &[-1..5]);
+    }
+
+    #[test]
+    fn range_map() {
+        let mut m = RangeMap::new();
+        m.insert(98..100, 50).unwrap();
+        m.insert(50..98, 52).unwrap();
+        assert_eq!(m.insert(40..51, 0), Err(50..98));
+        assert_eq!(m.insert(99..120, 0), Err(98..100));
+        let outputs: Vec<i64> = [0, 49, 50, 97, 98, 99, 100].map(|x| m.get(x)).to_vec();
+        assert_eq!(outputs, vec![0, 49, 52, 99, 50, 51, 100]);
+        assert_eq!(m.entry(99), Some((98..100, 50)));
+        assert_eq!(m.entry(100), None);
+
+        assert_eq!(m.map_range(45..52).ranges(), &[45..50, 52..54]);
+        assert_eq!(m.map_range(96..102).ranges(), &[50..52, 98..102]);
+        assert_eq!(m.map_range(0..10).ranges(), &[0..10]);
+        let mapped = m.map_set(&set(&[79..93, 55..68]));
+        assert_eq!(mapped.ranges(), &[57..70, 81..95]);
+    }
+}
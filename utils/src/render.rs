@@ -0,0 +1,340 @@
+//! Drawing grids for a person to look at, with the look of each cell picked
+//! by the caller: coloured text for a terminal, or PNG, SVG and animated GIF
+//! files.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::{Error, Grid};
+
+/// A 24-bit colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Self = Self(0, 0, 0);
+    pub const WHITE: Self = Self(255, 255, 255);
+    pub const GREY: Self = Self(110, 110, 110);
+    pub const RED: Self = Self(230, 70, 60);
+    pub const GREEN: Self = Self(90, 200, 90);
+    pub const BLUE: Self = Self(70, 120, 230);
+    pub const YELLOW: Self = Self(240, 200, 60);
+    pub const NAVY: Self = Self(25, 30, 70);
+}
+
+/// How a cell is drawn in a terminal. The default leaves the terminal's own
+/// colours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Style {
+    pub fg: Option<Rgb>,
+    pub bg: Option<Rgb>,
+    pub bold: bool,
+}
+
+impl Style {
+    pub fn fg(color: Rgb) -> Self { Self { fg: Some(color), ..Self::default() } }
+    pub fn with_bg(self, color: Rgb) -> Self { Self { bg: Some(color), ..self } }
+    pub fn bold(self) -> Self { Self { bold: true, ..self } }
+
+    /// The escape sequence switching to this style from the default one.
+    pub fn escape(&self) -> String {
+        let mut codes = Vec::new();
+        if self.bold { codes.push("1".to_owned()) }
+        if let Some(Rgb(r, g, b)) = self.fg { codes.push(format!("38;2;{r};{g};{b}")) }
+        if let Some(Rgb(r, g, b)) = self.bg { codes.push(format!("48;2;{r};{g};{b}")) }
+        format!("\x1b[{}m", codes.join(";"))
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+/// Draws `grid` with ANSI colours, one line per row. `cell` picks the
+/// character and style of the cell at `(x, y)`.
+pub fn ansi<T, F>(grid: &Grid<T>, mut cell: F) -> String
+    where F: FnMut((usize, usize), &T) -> (char, Style) {
+    let mut s = String::new();
+    for (y, row) in grid.rows().enumerate() {
+        let mut current = Style::default();
+        for (x, value) in row.iter().enumerate() {
+            let (c, style) = cell((x, y), value);
+            if style != current {
+                if current != Style::default() { s.push_str(RESET) }
+                if style != Style::default() { s.push_str(&style.escape()) }
+                current = style;
+            }
+            s.push(c);
+        }
+        if current != Style::default() { s.push_str(RESET) }
+        s.push('\n');
+    }
+    s
+}
+
+/// A line of samples of each style followed by what it marks.
+pub fn legend(entries: &[(Style, &str)]) -> String {
+    let mut s = String::new();
+    for (i, (style, label)) in entries.iter().enumerate() {
+        if i > 0 { s.push_str("  ") }
+        _ = write!(s, "{}  {RESET} {label}", style.escape());
+    }
+    s.push('\n');
+    s
+}
+
+/// The colour around frames smaller than the largest and between the frames
+/// of a still picture.
+const BACKGROUND: Rgb = Rgb::BLACK;
+/// How long each frame of a saved animation is shown.
+const FRAME_DELAY: Duration = Duration::from_millis(500);
+
+/// A picture, or an animation of several, with one colour per grid cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    frames: Vec<Grid<Rgb>>,
+}
+
+impl Picture {
+    /// A picture of `grid`, `color` picking the colour of the cell at
+    /// `(x, y)`.
+    pub fn new<T, F>(grid: &Grid<T>, color: F) -> Self
+        where F: FnMut((usize, usize), &T) -> Rgb {
+        let mut picture = Self { frames: Vec::new() };
+        picture.push(grid, color);
+        picture
+    }
+
+    /// Adds a frame of `grid` after the existing ones.
+    pub fn push<T, F>(&mut self, grid: &Grid<T>, mut color: F)
+        where F: FnMut((usize, usize), &T) -> Rgb {
+        let frame = Grid::from_fn(grid.width(), grid.height(), |x, y| color((x, y), &grid[(x, y)]));
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[Grid<Rgb>] { &self.frames }
+
+    /// The width and height of the largest frame, in cells.
+    fn size(&self) -> (usize, usize) {
+        let width = self.frames.iter().map(Grid::width).max().unwrap_or(0);
+        let height = self.frames.iter().map(Grid::height).max().unwrap_or(0);
+        (width, height)
+    }
+
+    /// Every frame side by side, one cell apart, for the formats that cannot
+    /// animate.
+    fn sheet(&self) -> Grid<Rgb> {
+        let width = self.frames.iter().map(|f| f.width() + 1).sum::<usize>();
+        let mut sheet = Grid::new(width.saturating_sub(1), self.size().1, BACKGROUND);
+        let mut left = 0;
+        for f in &self.frames {
+            paste(&mut sheet, f, left);
+            left += f.width() + 1;
+        }
+        sheet
+    }
+
+    /// Encodes the frames side by side as a PNG, each cell `scale` pixels
+    /// square.
+    pub fn to_png(&self, scale: usize) -> Result<Vec<u8>, Error> {
+        let sheet = self.sheet();
+        let too_large = |_| Error::new("picture too large for a PNG");
+        let width = u32::try_from(sheet.width() * scale).map_err(too_large)?;
+        let height = u32::try_from(sheet.height() * scale).map_err(too_large)?;
+        let mut data = Vec::new();
+        let mut encoder = png::Encoder::new(&mut data, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(encoding)?;
+        writer.write_image_data(&pixels(&sheet, scale)).map_err(encoding)?;
+        writer.finish().map_err(encoding)?;
+        Ok(data)
+    }
+
+    /// Draws the frames side by side as an SVG, each cell `scale` units
+    /// square.
+    pub fn to_svg(&self, scale: usize) -> String {
+        let sheet = self.sheet();
+        let (w, h) = (sheet.width(), sheet.height());
+        let mut s = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" \
+                             height=\"{}\" viewBox=\"0 0 {w} {h}\" \
+                             shape-rendering=\"crispEdges\">\n", w * scale, h * scale);
+        for (y, row) in sheet.rows().enumerate() {
+            // one rectangle per run of cells of the same colour
+            let mut x = 0;
+            for run in row.chunk_by(|a, b| a == b) {
+                let Rgb(r, g, b) = run[0];
+                _ = writeln!(s, "<rect x=\"{x}\" y=\"{y}\" width=\"{}\" height=\"1\" \
+                                 fill=\"#{r:02x}{g:02x}{b:02x}\"/>", run.len());
+                x += run.len();
+            }
+        }
+        s.push_str("</svg>\n");
+        s
+    }
+
+    /// Encodes the frames as a looping GIF animation showing each for
+    /// `delay`, each cell `scale` pixels square.
+    pub fn to_gif(&self, scale: usize, delay: Duration) -> Result<Vec<u8>, Error> {
+        let (width, height) = self.size();
+        let too_large = |_| Error::new("picture too large for a GIF");
+        let w = u16::try_from(width * scale).map_err(too_large)?;
+        let h = u16::try_from(height * scale).map_err(too_large)?;
+        let mut data = Vec::new();
+        let mut encoder = gif::Encoder::new(&mut data, w, h, &[]).map_err(encoding)?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(encoding)?;
+        for f in &self.frames {
+            let mut screen = Grid::new(width, height, BACKGROUND);
+            paste(&mut screen, f, 0);
+            let mut frame = gif_frame(w, h, &pixels(&screen, scale));
+            // GIF delays are in hundredths of a second
+            frame.delay = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+            encoder.write_frame(&frame).map_err(encoding)?;
+        }
+        encoder.into_inner().map_err(encoding)?;
+        Ok(data)
+    }
+
+    /// Saves the picture in the format named by the extension of `path`,
+    /// `.png`, `.svg` or `.gif`.
+    pub fn save(&self, path: &Path, scale: usize) -> Result<(), Error> {
+        let data = match path.extension().and_then(|x| x.to_str()) {
+            Some("png") => self.to_png(scale),
+            Some("svg") => Ok(self.to_svg(scale).into_bytes()),
+            Some("gif") => self.to_gif(scale, FRAME_DELAY),
+            _ => Err(Error::new("expected a .png, .svg or .gif file")),
+        };
+        data.and_then(|x| std::fs::write(path, x).map_err(Error::from))
+            .map_err(|e| e.in_file(path.display()))
+    }
+}
+
+fn encoding(e: impl Display) -> Error { Error::new(format!("cannot encode the picture: {e}")) }
+
+/// Copies `frame` onto `onto`, `left` cells from its left edge.
+fn paste(onto: &mut Grid<Rgb>, frame: &Grid<Rgb>, left: usize) {
+    for ((x, y), &c) in frame.iter() { onto[(left + x, y)] = c }
+}
+
+/// The RGB bytes of `grid`, row by row, each cell `scale` pixels square.
+fn pixels(grid: &Grid<Rgb>, scale: usize) -> Vec<u8> {
+    let mut v = Vec::with_capacity(grid.width() * grid.height() * scale * scale * 3);
+    for row in grid.rows() {
+        let line: Vec<u8> = row.iter().flat_map(|&Rgb(r, g, b)| [r, g, b].repeat(scale)).collect();
+        for _ in 0..scale { v.extend_from_slice(&line) }
+    }
+    v
+}
+
+/// A GIF frame of the RGB bytes `rgb`, with an exact palette when there are
+/// at most 256 colours and quantized otherwise.
+fn gif_frame(width: u16, height: u16, rgb: &[u8]) -> gif::Frame<'static> {
+    let mut palette: Vec<[u8; 3]> = Vec::new();
+    let mut index = HashMap::new();
+    let mut buffer = Vec::with_capacity(rgb.len() / 3);
+    for px in rgb.chunks_exact(3) {
+        let px = [px[0], px[1], px[2]];
+        let i = *index.entry(px).or_insert_with(|| {
+            palette.push(px);
+            palette.len() - 1
+        });
+        if i > u8::MAX as usize { return gif::Frame::from_rgb(width, height, rgb) }
+        buffer.push(i as u8);
+    }
+    gif::Frame {
+        width,
+        height,
+        buffer: buffer.into(),
+        palette: Some(palette.concat()),
+        ..gif::Frame::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes() {
+        let grid = Grid::parse("ab.\n..c", Some).unwrap();
+        let s = ansi(&grid, |_, &c| match c {
+            '.' => (' ', Style::default()),
+            'c' => ('c', Style::fg(Rgb::RED).with_bg(Rgb::BLACK).bold()),
+            _ => (c, Style::fg(Rgb::GREEN)),
+        });
+        assert_eq!(s, "\x1b[38;2;90;200;90mab\x1b[0m \n  \
+                       \x1b[1;38;2;230;70;60;48;2;0;0;0mc\x1b[0m\n");
+
+        let plain = ansi(&grid, |_, &c| (c, Style::default()));
+        assert_eq!(plain, "ab.\n..c\n");
+        assert_eq!(legend(&[(Style::default().with_bg(Rgb::BLUE), "x")]),
+                   "\x1b[48;2;70;120;230m  \x1b[0m x\n");
+    }
+
+    fn picture() -> Picture {
+        let grid = Grid::parse("#.\n.#", Some).unwrap();
+        let color = |_, c: &char| if *c == '#' { Rgb::RED } else { Rgb::WHITE };
+        let mut picture = Picture::new(&grid, color);
+        picture.push(&Grid::parse("##.", Some).unwrap(), color);
+        picture
+    }
+
+    #[test]
+    fn frames() {
+        let p = picture();
+        assert_eq!(p.frames().len(), 2);
+        assert_eq!(p.frames()[0][(0, 0)], Rgb::RED);
+        assert_eq!(p.size(), (3, 2));
+        let sheet = p.sheet();
+        assert_eq!((sheet.width(), sheet.height()), (6, 2));
+        assert_eq!(sheet.row(0), &[Rgb::RED, Rgb::WHITE, BACKGROUND, Rgb::RED, Rgb::RED,
+                                   Rgb::WHITE]);
+        assert_eq!(sheet.row(1)[3..], [BACKGROUND; 3]);
+    }
+
+    #[test]
+    fn png() {
+        let data = picture().to_png(2).unwrap();
+        let mut reader = png::Decoder::new(std::io::Cursor::new(data)).read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut buf).unwrap();
+        assert_eq!((info.width, info.height), (12, 4));
+        // the second pixel of the first row is still the first, red, cell
+        assert_eq!(&buf[3..6], &[230, 70, 60]);
+        assert_eq!(&buf[6..9], &[255, 255, 255]);
+    }
+
+    #[test]
+    fn svg() {
+        let s = picture().to_svg(10);
+        assert!(s.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"60\" \
+                               height=\"20\" viewBox=\"0 0 6 2\""));
+        assert!(s.contains("<rect x=\"3\" y=\"0\" width=\"2\" height=\"1\" fill=\"#e6463c\"/>"));
+        assert_eq!(s.matches("<rect").count(), 8);
+    }
+
+    #[test]
+    fn gif() {
+        let data = picture().to_gif(1, Duration::from_millis(250)).unwrap();
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(std::io::Cursor::new(data)).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (3, 2));
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 25);
+            frames.push(frame.buffer.to_vec());
+        }
+        assert_eq!(frames.len(), 2);
+        // the second frame is one row high, padded with the background
+        assert_eq!(&frames[1][..4], &[230, 70, 60, 255]);
+        assert_eq!(&frames[1][12..16], &[0, 0, 0, 255]);
+        assert_eq!(&frames[0][4..8], &[255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn save() {
+        let e = picture().save(Path::new("picture.bmp"), 1).unwrap_err();
+        assert_eq!(e.to_string(), "picture.bmp: expected a .png, .svg or .gif file");
+    }
+}
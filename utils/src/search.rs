@@ -0,0 +1,198 @@
+//! Shortest paths over graphs described by a neighbour function, so the
+//! nodes can be grid positions, positions with extra state, or anything else
+//! hashable.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A shortest path and its total cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub cost: C,
+    /// Every node from the start to the goal, both included.
+    pub nodes: Vec<N>,
+}
+
+/// The nodes seen so far, with the cheapest known way to reach each of them.
+struct Visited<N, C> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    parent: Vec<Option<usize>>,
+    cost: Vec<C>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> Visited<N, C> {
+    fn new(start: N, cost: C) -> Self {
+        Self {
+            nodes: vec![start.clone()],
+            index: HashMap::from([(start, 0)]),
+            parent: vec![None],
+            cost: vec![cost],
+        }
+    }
+    /// Records reaching `node` from `parent` at `cost` if that is cheaper
+    /// than any known way, returning its index.
+    fn relax(&mut self, node: N, parent: usize, cost: C) -> Option<usize>
+        where C: Ord {
+        match self.index.entry(node) {
+            Entry::Occupied(e) => {
+                let i = *e.get();
+                if self.cost[i] <= cost { return None }
+                self.cost[i] = cost;
+                self.parent[i] = Some(parent);
+                Some(i)
+            },
+            Entry::Vacant(e) => {
+                let i = self.nodes.len();
+                self.nodes.push(e.key().clone());
+                e.insert(i);
+                self.parent.push(Some(parent));
+                self.cost.push(cost);
+                Some(i)
+            },
+        }
+    }
+    fn path(&self, mut i: usize) -> Path<N, C> {
+        let cost = self.cost[i];
+        let mut nodes = vec![self.nodes[i].clone()];
+        while let Some(p) = self.parent[i] {
+            nodes.push(self.nodes[p].clone());
+            i = p;
+        }
+        nodes.reverse();
+        Path { cost, nodes }
+    }
+}
+
+/// Finds a path with the fewest steps from `start` to the first node for
+/// which `goal` holds. Every step costs 1.
+pub fn bfs<N, F, I, G>(start: N, mut neighbours: F, mut goal: G) -> Option<Path<N, usize>>
+    where N: Eq + Hash + Clone,
+          F: FnMut(&N) -> I,
+          I: IntoIterator<Item = N>,
+          G: FnMut(&N) -> bool {
+    let mut visited = Visited::new(start, 0);
+    let mut queue = VecDeque::from([0]);
+    while let Some(i) = queue.pop_front() {
+        if goal(&visited.nodes[i]) { return Some(visited.path(i)) }
+        let cost = visited.cost[i] + 1;
+        for n in neighbours(&visited.nodes[i]) {
+            if visited.index.contains_key(&n) { continue }
+            queue.extend(visited.relax(n, i, cost));
+        }
+    }
+    None
+}
+
+/// Finds a cheapest path from `start` to the first node for which `goal`
+/// holds. `neighbours` yields each neighbour with the cost of stepping to it.
+pub fn dijkstra<N, C, F, I, G>(start: N, neighbours: F, goal: G) -> Option<Path<N, C>>
+    where N: Eq + Hash + Clone,
+          C: Copy + Ord + Default + Add<Output = C>,
+          F: FnMut(&N) -> I,
+          I: IntoIterator<Item = (N, C)>,
+          G: FnMut(&N) -> bool {
+    astar(start, neighbours, |_| C::default(), goal)
+}
+
+/// Like [`dijkstra`], exploring nodes in the order of their cost plus
+/// `heuristic`, an estimate of the remaining cost to the goal. The path is
+/// only guaranteed cheapest if the estimate never exceeds the actual cost.
+pub fn astar<N, C, F, I, H, G>(start: N, mut neighbours: F, mut heuristic: H,
+                               mut goal: G) -> Option<Path<N, C>>
+    where N: Eq + Hash + Clone,
+          C: Copy + Ord + Default + Add<Output = C>,
+          F: FnMut(&N) -> I,
+          I: IntoIterator<Item = (N, C)>,
+          H: FnMut(&N) -> C,
+          G: FnMut(&N) -> bool {
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut visited = Visited::new(start, C::default());
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        // a cheaper way to this node was found after this entry was queued
+        if cost > visited.cost[i] { continue }
+        if goal(&visited.nodes[i]) { return Some(visited.path(i)) }
+        for (n, step) in neighbours(&visited.nodes[i]) {
+            let estimate = heuristic(&n);
+            let cost = cost + step;
+            if let Some(j) = visited.relax(n, i, cost) {
+                heap.push(Reverse((cost + estimate, cost, j)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::Point;
+    use crate::Grid;
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    fn maze() -> (Grid<char>, Point, Point) {
+        let grid = Grid::parse(MAZE, Some).unwrap();
+        let find = |c| Point::from(grid.iter().find(|x| *x.1 == c).unwrap().0);
+        let (start, end) = (find('S'), find('E'));
+        (grid, start, end)
+    }
+
+    fn open(grid: &Grid<char>, p: &Point) -> Vec<Point> {
+        p.neighbours4().filter(|&n| grid.at(n).is_some_and(|&c| c != '#')).collect()
+    }
+
+    #[test]
+    fn breadth_first() {
+        let (grid, start, end) = maze();
+        let path = bfs(start, |p| open(&grid, p), |p| *p == end).unwrap();
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.nodes.len(), 16);
+        assert_eq!((path.nodes[0], path.nodes[15]), (start, end));
+        assert!(path.nodes.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+        assert!(path.nodes.iter().all(|&p| grid[p] != '#'));
+
+        let walled = Grid::parse("S#E", Some).unwrap();
+        let start = Point::new(0, 0);
+        assert_eq!(bfs(start, |p| open(&walled, p), |p| walled[*p] == 'E'), None);
+        let path = bfs(start, |p| open(&walled, p), |_| true).unwrap();
+        assert_eq!(path, Path { cost: 0, nodes: vec![start] });
+    }
+
+    #[test]
+    fn weighted() {
+        // digits are the cost of entering a cell
+        let grid = Grid::parse("1911\n1919\n1111", |c| c.to_digit(10).map(u64::from)).unwrap();
+        let end = Point::new(3, 0);
+        let neighbours = |p: &Point| {
+            p.neighbours4().filter_map(|n| grid.at(n).map(|&c| (n, c))).collect::<Vec<_>>()
+        };
+        let path = dijkstra(Point::ORIGIN, neighbours, |p| *p == end).unwrap();
+        assert_eq!(path.cost, 7);
+        assert_eq!(path.nodes.len(), 8);
+
+        let path = astar(Point::ORIGIN, neighbours, |p| p.manhattan(end), |p| *p == end)
+            .unwrap();
+        assert_eq!(path.cost, 7);
+        assert_eq!(path.nodes.last(), Some(&end));
+    }
+
+    #[test]
+    fn matches_bfs() {
+        let (grid, start, end) = maze();
+        let steps = |p: &Point| open(&grid, p).into_iter().map(|n| (n, 1usize));
+        let expected = bfs(start, |p| open(&grid, p), |p| *p == end).unwrap().cost;
+        let path = dijkstra(start, steps, |p| *p == end).unwrap();
+        assert_eq!(path.cost, expected);
+        let path = astar(start, steps, |p| p.manhattan(end) as usize, |p| *p == end).unwrap();
+        assert_eq!(path.cost, expected);
+    }
+}
@@ -0,0 +1,108 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{parse_token, Error};
+
+/// Returns the whitespace-separated tokens of `text`.
+fn tokens(text: &str) -> impl Iterator<Item = &str> {
+    text.split(char::is_whitespace).filter(|x| !x.is_empty())
+}
+
+/// Parses every whitespace-separated token of `text`.
+pub fn values<T>(text: &str) -> Result<Vec<T>, Error>
+    where T: FromStr, T::Err: Display {
+    tokens(text).map(|x| parse_token(text, x)).collect()
+}
+
+/// Parses exactly `N` whitespace-separated values from `text`.
+pub fn record<const N: usize, T>(text: &str) -> Result<[T; N], Error>
+    where T: FromStr, T::Err: Display {
+    let values: Vec<T> = values(text)?;
+    let len = values.len();
+    values.try_into().map_err(|_| {
+        Error::at(text, &text[text.len()..], format!("expected {N} values, found {len}"))
+    })
+}
+
+/// Splits a `label: rest` line, checking its label, and returns the trimmed
+/// rest.
+pub fn split_label<'a>(line: &'a str, label: &str) -> Result<&'a str, Error> {
+    match line.strip_prefix(label).and_then(|x| x.strip_prefix(':')) {
+        Some(rest) => Ok(rest.trim()),
+        None => Err(Error::at(line, line, format!("expected `{label}:`"))),
+    }
+}
+
+/// Splits a `Label N: rest` line, checking its label, and returns `N` and
+/// the trimmed rest.
+pub fn split_header<'a, T>(line: &'a str, label: &str) -> Result<(T, &'a str), Error>
+    where T: FromStr, T::Err: Display {
+    let Some((head, rest)) = line.split_once(':') else {
+        return Err(Error::at(line, &line[line.len()..], "expected `:`"));
+    };
+    let Some(n) = head.strip_prefix(label).filter(|x| x.starts_with(' ')) else {
+        return Err(Error::at(line, line, format!("expected `{label} N:`")));
+    };
+    Ok((parse_token(line, n.trim())?, rest.trim()))
+}
+
+/// Splits `input` into the paragraphs separated by blank lines, skipping
+/// leading, trailing and repeated blank lines. Each section is a subslice of
+/// `input` without its final line break.
+pub fn sections(input: &str) -> Vec<&str> {
+    let mut v = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut end = 0;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(s) = start.take() { v.push(&input[s..end]) }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end_matches(['\n', '\r']).len();
+        }
+        offset += line.len();
+    }
+    if let Some(s) = start { v.push(&input[s..end]) }
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_values() {
+        assert_eq!(values::<u8>(" 1  2\t3 ").unwrap(), vec![1, 2, 3]);
+        let e = values::<u8>("1 2 x").unwrap_err();
+        assert_eq!(e.to_string(), "1:5: invalid value `x`: invalid digit found in string");
+        assert_eq!(record::<3, u64>("50 98 2").unwrap(), [50, 98, 2]);
+        let e = record::<3, u64>("50 98").unwrap_err();
+        assert_eq!(e.to_string(), "1:6: expected 3 values, found 2");
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(split_label("Time:   7  15", "Time").unwrap(), "7  15");
+        let e = split_label("Distance: 9", "Time").unwrap_err();
+        assert_eq!(e.to_string(), "1:1: expected `Time:`");
+
+        assert_eq!(split_header::<u32>("Card   12: 1 | 2", "Card").unwrap(), (12, "1 | 2"));
+        let e = split_header::<u32>("Card x: 1", "Card").unwrap_err();
+        assert_eq!(e.column, Some(6));
+        let e = split_header::<u32>("Card 1 41", "Card").unwrap_err();
+        assert_eq!(e.to_string(), "1:10: expected `:`");
+        assert!(split_header::<u32>("Game 1: 41", "Card").is_err());
+        assert!(split_header::<u32>("Card1: 41", "Card").is_err());
+    }
+
+    #[test]
+    fn split_sections() {
+        let input = "\na\nb\n\n\nc\r\n\r\nd\n";
+        let s = sections(input);
+        assert_eq!(s, vec!["a\nb", "c", "d"]);
+        let e = Error::at(s[1], s[1], "x").within(input, s[1]);
+        assert_eq!(e.line, Some(6));
+        assert!(sections("\n\n").is_empty());
+    }
+}